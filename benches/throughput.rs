@@ -0,0 +1,57 @@
+// Throughput of the SHA-1 and SHA-2 hashers: `cargo bench`
+//
+// Hashes a 1 MiB buffer repeatedly for each algorithm and prints MiB/s. A name
+// given after `--` only runs the algorithms whose name contains it.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use sha_algorithm::{Sha1Hasher, Sha2Hasher, ShaAlgorithm};
+
+const BUFFER_SIZE: usize = 1 << 20;
+const MIN_TIME: Duration = Duration::from_secs(2);
+
+fn hash(algorithm: &ShaAlgorithm, data: &[u8]) -> usize {
+    let mut out = [0u8; 64];
+    match algorithm {
+        ShaAlgorithm::SHA1 => {
+            let mut hasher = Sha1Hasher::new();
+            hasher.update(data).unwrap();
+            hasher.finalize_into(&mut out).unwrap()
+        },
+        _ => {
+            let mut hasher = Sha2Hasher::new(algorithm).unwrap();
+            hasher.update(data).unwrap();
+            hasher.finalize_into(&mut out).unwrap()
+        },
+    }
+}
+
+fn main() {
+    let filter = std::env::args().skip(1).find(|arg| !arg.starts_with('-'));
+    let data: Vec<u8> = (0..BUFFER_SIZE).map(|i| (i * 31 + 7) as u8).collect();
+    let algorithms = [
+        ShaAlgorithm::SHA1,
+        ShaAlgorithm::SHA224,
+        ShaAlgorithm::SHA256,
+        ShaAlgorithm::SHA384,
+        ShaAlgorithm::SHA512,
+        ShaAlgorithm::SHA512T(256),
+    ];
+
+    for algorithm in algorithms.iter() {
+        let name = algorithm.to_string();
+        if filter.as_ref().is_some_and(|filter| !name.to_lowercase().contains(&filter.to_lowercase())) {
+            continue;
+        }
+        black_box(hash(algorithm, &data));
+        let start = Instant::now();
+        let mut iterations = 0u32;
+        while start.elapsed() < MIN_TIME {
+            black_box(hash(algorithm, black_box(&data)));
+            iterations += 1;
+        }
+        let elapsed = start.elapsed();
+        let mib_per_sec = iterations as f64 / elapsed.as_secs_f64();
+        println!("{:<12} {:>8.1} MiB/s  ({} MiB in {:.2?})", name, mib_per_sec, iterations, elapsed);
+    }
+}
@@ -0,0 +1,40 @@
+// Runs NIST CAVP response files and reports the result of every vector
+
+use sha_algorithm::cavp;
+use sha_algorithm::ShaAlgorithm;
+use super::{EXIT_FAILURE, EXIT_SUCCESS};
+
+pub struct CavpOptions {
+    pub algorithm: Option<ShaAlgorithm>,
+    pub files: Vec<String>,
+    pub quiet: bool,
+}
+
+pub fn cavp(options: &CavpOptions) -> i32 {
+    let mut status = EXIT_SUCCESS;
+    for file in options.files.iter() {
+        let results = match cavp::run_file(file, options.algorithm) {
+            Ok(results) => results,
+            Err(e) => {
+                eprintln!("sha_algorithm: {}: {}", file, e);
+                status = EXIT_FAILURE;
+                continue;
+            },
+        };
+        let mut failed = 0;
+        for result in results.iter() {
+            if !result.passed {
+                failed += 1;
+            }
+            if !options.quiet || !result.passed {
+                let verdict = if result.passed { "PASS" } else { "FAIL" };
+                println!("{}:{}: {}: {}", file, result.entry.line, result.entry.vector, verdict);
+            }
+        }
+        println!("{}: {} passed, {} failed", file, results.len() - failed, failed);
+        if failed > 0 {
+            status = EXIT_FAILURE;
+        }
+    }
+    status
+}
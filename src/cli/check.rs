@@ -0,0 +1,199 @@
+// Verification of sha256sum-style checksum lists
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use sha_algorithm::hasher;
+use sha_algorithm::{Digest, ShaAlgorithm};
+use super::{HashOptions, Input, EXIT_FAILURE, EXIT_SUCCESS};
+
+struct ChecksumLine {
+    algorithm: Option<ShaAlgorithm>,
+    digest: Digest,
+    name: String,
+}
+
+#[derive(Default)]
+struct Summary {
+    verified: usize,
+    failed: usize,
+    unreadable: usize,
+    improper: usize,
+    unknown: usize,
+}
+
+pub fn check(options: &HashOptions) -> i32 {
+    let mut status = EXIT_SUCCESS;
+    for input in options.inputs.iter() {
+        let list_status = match input {
+            Input::File(path) => match File::open(path) {
+                Ok(file) => check_list(BufReader::new(file), path, options),
+                Err(e) => {
+                    eprintln!("sha_algorithm: {}: {}", path, e);
+                    EXIT_FAILURE
+                },
+            },
+            Input::Stdin => check_list(std::io::stdin().lock(), "-", options),
+            Input::Text(_) => EXIT_FAILURE,
+        };
+        if list_status != EXIT_SUCCESS {
+            status = list_status;
+        }
+    }
+    status
+}
+
+fn check_list<R: BufRead>(reader: R, list_name: &str, options: &HashOptions) -> i32 {
+    let mut summary = Summary::default();
+    for line in reader.lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("sha_algorithm: {}: {}", list_name, e);
+                return EXIT_FAILURE;
+            },
+        };
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let entry = match parse_line(line) {
+            Some(entry) => entry,
+            None => {
+                summary.improper += 1;
+                continue;
+            },
+        };
+        let named = options.algorithm
+            .or(entry.algorithm)
+            .or_else(|| algorithm_from_list_name(list_name));
+        let algorithm = match named.or_else(|| algorithm_from_digest_length(entry.digest.len())) {
+            Some(algorithm) if algorithm.output_size() == entry.digest.len() => algorithm,
+            _ => {
+                summary.improper += 1;
+                continue;
+            },
+        };
+
+        summary.verified += 1;
+        match hasher::hash_file(&entry.name, &algorithm) {
+            Ok(hash) if hash.digest() == entry.digest => {
+                if !options.quiet {
+                    println!("{}: OK", entry.name);
+                }
+            },
+            // A digest of another algorithm with the same length, such as SHA-512/256 for SHA-256
+            Ok(_) if named.is_none() => {
+                summary.unknown += 1;
+                println!("{}: unknown algorithm", entry.name);
+            },
+            Ok(_) => {
+                summary.failed += 1;
+                println!("{}: FAILED", entry.name);
+            },
+            Err(e) => {
+                summary.unreadable += 1;
+                eprintln!("sha_algorithm: {}: {}", entry.name, e);
+                println!("{}: FAILED open or read", entry.name);
+            },
+        }
+    }
+    report(&summary, list_name)
+}
+
+fn report(summary: &Summary, list_name: &str) -> i32 {
+    if summary.verified == 0 {
+        eprintln!("sha_algorithm: {}: no properly formatted checksum lines found", list_name);
+        return EXIT_FAILURE;
+    }
+    let plural = |n: usize, one: &'static str, many: &'static str| if n == 1 { one } else { many };
+    if summary.improper > 0 {
+        eprintln!("sha_algorithm: WARNING: {} {} improperly formatted",
+            summary.improper, plural(summary.improper, "line is", "lines are"));
+    }
+    if summary.unreadable > 0 {
+        eprintln!("sha_algorithm: WARNING: {} listed {} could not be read",
+            summary.unreadable, plural(summary.unreadable, "file", "files"));
+    }
+    if summary.failed > 0 {
+        eprintln!("sha_algorithm: WARNING: {} computed {} did NOT match",
+            summary.failed, plural(summary.failed, "checksum", "checksums"));
+    }
+    if summary.unknown > 0 {
+        eprintln!("sha_algorithm: WARNING: {} {} not match the algorithm guessed from {} length; name it with --algo",
+            summary.unknown, plural(summary.unknown, "checksum does", "checksums do"), plural(summary.unknown, "its", "their"));
+    }
+    if summary.failed > 0 || summary.unreadable > 0 || summary.unknown > 0 {
+        EXIT_FAILURE
+    } else {
+        EXIT_SUCCESS
+    }
+}
+
+// Parses '<digest>  <name>', '<digest> *<name>' and the BSD '<ALGO> (<name>) = <digest>' form
+fn parse_line(line: &str) -> Option<ChecksumLine> {
+    let (escaped, line) = match line.strip_prefix('\\') {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+
+    let (algorithm, digest, name) = match line.split_once(" (") {
+        Some((algorithm, rest)) if line.contains(") = ") => {
+            let (name, digest) = rest.rsplit_once(") = ")?;
+            (Some(algorithm.parse::<ShaAlgorithm>().ok()?), digest, name)
+        },
+        _ => {
+            let (digest, rest) = line.split_once(' ')?;
+            let name = rest.strip_prefix(' ').or_else(|| rest.strip_prefix('*'))?;
+            (None, digest, name)
+        },
+    };
+    if name.is_empty() {
+        return None;
+    }
+    let name = if escaped { unescape(name)? } else { name.to_string() };
+    Some(ChecksumLine { algorithm, digest: digest.parse().ok()?, name })
+}
+
+fn unescape(name: &str) -> Option<String> {
+    let mut result = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                '\\' => result.push('\\'),
+                'n' => result.push('\n'),
+                _ => return None,
+            },
+            _ => result.push(c),
+        }
+    }
+    Some(result)
+}
+
+// 'release.sha256', 'SHA256SUMS' or 'a.sha512_256' name the algorithm of their lines
+fn algorithm_from_list_name(list_name: &str) -> Option<ShaAlgorithm> {
+    let path = Path::new(list_name);
+    if let Some(algorithm) = path.extension().and_then(|ext| ext.to_str()).and_then(algorithm_from_name) {
+        return Some(algorithm);
+    }
+    let stem = path.file_stem()?.to_str()?.to_ascii_lowercase();
+    algorithm_from_name(stem.strip_suffix("sums").or_else(|| stem.strip_suffix("sum"))?)
+}
+
+// File names can't hold '/', so SHA-512/t is written 'sha512_256' or 'sha512-256' there
+fn algorithm_from_name(name: &str) -> Option<ShaAlgorithm> {
+    name.parse().ok().or_else(|| name.replacen(['_', '-'], "/", 1).parse().ok())
+}
+
+// SHA-512/t and SHA-3 digests share their lengths with SHA-1 and SHA-2, so they are never guessed
+fn algorithm_from_digest_length(len: usize) -> Option<ShaAlgorithm> {
+    match len {
+        20 => Some(ShaAlgorithm::SHA1),
+        28 => Some(ShaAlgorithm::SHA224),
+        32 => Some(ShaAlgorithm::SHA256),
+        48 => Some(ShaAlgorithm::SHA384),
+        64 => Some(ShaAlgorithm::SHA512),
+        _ => None,
+    }
+}
@@ -0,0 +1,193 @@
+// Interactive menu, used when the program is run without arguments
+
+use sha_algorithm::sha1;
+use sha_algorithm::sha2;
+use sha_algorithm::types;
+use sha_algorithm::err_handling;
+use sha_algorithm::hasher;
+
+
+pub fn menu() {
+    'mainLoop: loop {
+        println!();
+        let mut option;
+        loop {
+            println!("Welcome to Rust Hashing CLI");
+            println!("1. SHA-1");
+            println!("2. SHA-224");
+            println!("3. SHA-256");
+            println!("4. SHA-384");
+            println!("5. SHA-512");
+            println!("6. SHA-512/t");
+            println!("7. SHA3-224");
+            println!("8. SHA3-256");
+            println!("9. SHA3-384");
+            println!("10. SHA3-512");
+            println!("11. SHAKE128");
+            println!("12. SHAKE256");
+            println!("13. Exit");
+            print!("Select the algorithm you want to use: ");
+
+            option = get_number();
+            if (option < 13) & (option > 0) {
+                break;
+            } else if option == 13 {
+                println!("Exiting...");
+                break 'mainLoop;
+            } else {
+                println!("Invalid option");
+            }
+        }
+
+        let algorithm = match option {
+            1 => types::wrappers::ShaAlgorithm::SHA1,
+            2 => types::wrappers::ShaAlgorithm::SHA224,
+            3 => types::wrappers::ShaAlgorithm::SHA256,
+            4 => types::wrappers::ShaAlgorithm::SHA384,
+            5 => types::wrappers::ShaAlgorithm::SHA512,
+            7 => types::wrappers::ShaAlgorithm::SHA3_224,
+            8 => types::wrappers::ShaAlgorithm::SHA3_256,
+            9 => types::wrappers::ShaAlgorithm::SHA3_384,
+            10 => types::wrappers::ShaAlgorithm::SHA3_512,
+            11 | 12 => {
+                let mut bits;
+                loop {
+                    print!("Enter the output length in bits: ");
+                    bits = get_number();
+                    if (bits == 0) || !bits.is_multiple_of(8) {
+                        println!("The length must be a positive multiple of 8");
+                    } else {
+                        break;
+                    }
+                }
+                if option == 11 {
+                    types::wrappers::ShaAlgorithm::SHAKE128(bits as u32)
+                } else {
+                    types::wrappers::ShaAlgorithm::SHAKE256(bits as u32)
+                }
+            },
+            _ => {
+                let mut t;
+                loop{
+                    print!("Enter the value of t: ");
+                    t = get_number();
+                    if !(1..512).contains(&t) || !t.is_multiple_of(8) || (t == 384) {
+                        println!("t must be a multiple of 8 and between 1 and 512");
+                        continue;
+                    } else {
+                        break;
+                    }
+                    
+                }
+                types::wrappers::ShaAlgorithm::SHA512T(t)
+            },
+        };
+
+        let mut source;
+        loop {
+            println!("1. Text");
+            println!("2. File");
+            println!("3. Standard input");
+            print!("Select what you want to hash: ");
+
+            source = get_number();
+            if (source < 4) & (source > 0) {
+                break;
+            } else {
+                println!("Invalid option");
+            }
+        }
+
+        let hash = match source {
+            1 => {
+                print!("Enter the message you want to hash: ");
+                let message = get_user_input();
+                hasher::hash_bytes(message.as_bytes(), &algorithm)
+            },
+            2 => {
+                print!("Enter the path of the file you want to hash: ");
+                let path = get_user_input();
+                hasher::hash_file(&path, &algorithm)
+            },
+            3 => {
+                println!("Reading standard input until EOF (Ctrl-D, or Ctrl-Z then Enter on Windows)...");
+                hasher::hash_reader(std::io::stdin().lock(), &algorithm)
+            },
+            _ => Err(err_handling::ShaError::CustomError("Invalid input source".to_string())),
+        };
+        let hash = match hash {
+            Ok(h) => h,
+            Err(e) => {
+                println!("Error: {}",e);
+                println!("Press any key to continue");
+                get_user_input();
+                clear_console();
+                continue;
+            },
+        };
+        println!("Hash value:\n{}",hash.digest());
+        println!("Press any key to continue");
+        get_user_input();
+        clear_console();
+    }
+}
+
+fn clear_console() {
+    use std::io::Write;
+    print!("\x1B[2J\x1B[H");
+    std::io::stdout().flush().unwrap(); // Ensure the command is flushed immediately
+}
+
+fn get_user_input() -> String {
+    use std::io::Write;
+    std::io::stdout().flush().unwrap();
+    let mut buf: String = String::new();
+    let read = std::io::stdin().read_line(&mut buf).expect("Unable to read line\n");
+    if read == 0 {
+        // Standard input is closed, nothing else can be asked
+        println!();
+        println!("Exiting...");
+        std::process::exit(0);
+    }
+    let ret = buf.trim().to_string();
+    buf.clear();
+    ret
+}
+
+fn get_number() -> u16 {
+    loop {
+        let number = get_user_input().parse::<u16>();
+        match number {
+            Ok(n) => return n,
+            Err(_) => println!("Invalid number"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn test() {
+    let buf: &mut String = &mut (String::new());
+    std::io::stdin().read_line(buf).expect("Unable to read line\n");
+    let string = buf.trim().to_owned();
+    buf.clear();
+
+    let hash = sha1::hash_message(& string,&types::wrappers::ShaAlgorithm::SHA1);
+    println!("{:x?}",hash);
+    let hash = sha2::hash_message(& string,&types::wrappers::ShaAlgorithm::SHA224);
+    println!("{:x?}",hash);
+    let hash = sha2::hash_message(& string,&types::wrappers::ShaAlgorithm::SHA256);
+    println!("{:x?}",hash);
+    let hash = sha2::hash_message(& string,&types::wrappers::ShaAlgorithm::SHA384);
+    println!("{:x?}",hash);
+    let hash = sha2::hash_message(& string,&types::wrappers::ShaAlgorithm::SHA512);
+    println!("{:x?}",hash);
+    let hash = sha2::hash_message(& string,&types::wrappers::ShaAlgorithm::SHA512T(224));
+    println!("{:x?}",hash);
+}
+
+#[derive(Debug)]
+#[allow(dead_code)]
+pub enum MenuError {
+    InvalidOption,
+}
+
@@ -0,0 +1,268 @@
+// Non-interactive command-line interface
+
+pub mod menu;
+mod cavp;
+mod check;
+mod trace;
+
+use sha_algorithm::hasher::{self, Hasher};
+use sha_algorithm::{Digest, HashResult, ShaAlgorithm, ShaError};
+
+pub const EXIT_SUCCESS: i32 = 0;
+pub const EXIT_FAILURE: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+
+const USAGE: &str = "\
+Usage:
+  sha_algorithm                                   Start the interactive menu
+  sha_algorithm hash [OPTIONS] [FILE]...          Print the digest of each input
+  sha_algorithm hash --check [OPTIONS] [FILE]...  Verify the checksums listed in each FILE
+  sha_algorithm cavp [--algo <ALGO>] [--quiet] FILE...
+                                                  Run the vectors of NIST CAVP .rsp files
+  sha_algorithm trace [--algo <ALGO>] [--format <FMT>] [--string <TEXT> | FILE]
+                                                  Show every round of a SHA-1 or SHA-2 hash
+
+Options:
+  -a, --algo <ALGO>     sha1, sha224, sha256, sha384, sha512, sha512/<t>,
+                        sha3-224, sha3-256, sha3-384, sha3-512, shake128 or shake256 (default: sha256)
+  -l, --length <BITS>   Output length of shake128 and shake256 (default: 128 and 256)
+  -s, --string <TEXT>   Hash TEXT instead of a file (can be repeated)
+  -b, --binary          Mark files with '*' (binary mode) in the output
+  -t, --text            Mark files with ' ' (text mode) in the output (default)
+  -c, --check           Read checksum lists and verify the files they name
+      --quiet           With --check, don't print OK for each verified file
+  -h, --help            Show this help
+
+With no FILE and no --string, or when FILE is -, standard input is read.
+Output lines are in the sha256sum format: '<digest>  <file>', or '<digest> *<file>' in binary mode.
+With --check and no --algo, the algorithm is taken from the list's name (.sha1, .sha512_256, SHA256SUMS, ...)
+or from the digest length. SHA-512/t and SHA-3 lists need --algo unless the name gives the algorithm;
+a digest that doesn't match the algorithm guessed from its length is reported as an unknown algorithm.
+
+The cavp command runs SHA-1 and SHA-2 ShortMsg, LongMsg and Monte files, byte or bit oriented, and HMAC.rsp.
+It prints PASS or FAIL for every vector (only FAIL with --quiet) and a summary for each file.
+
+The trace command prints the message schedule W[t], the working variables after every round and the
+intermediate hash values, laid out like the FIPS 180 examples (--format table, the default),
+or exports them with --format json or --format csv.
+
+Exit status: 0 on success, 1 if any input could not be hashed or verified, 2 on usage errors.";
+
+enum Command {
+    Help,
+    Hash(HashOptions),
+    Cavp(cavp::CavpOptions),
+    Trace(trace::TraceOptions),
+}
+
+struct HashOptions {
+    algorithm: Option<ShaAlgorithm>,
+    inputs: Vec<Input>,
+    binary: bool,
+    check: bool,
+    quiet: bool,
+}
+
+enum Input {
+    Text(String),
+    File(String),
+    Stdin,
+}
+
+pub fn run(args: &[String]) -> i32 {
+    match parse_args(args) {
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            EXIT_SUCCESS
+        },
+        Ok(Command::Hash(options)) if options.check => check::check(&options),
+        Ok(Command::Hash(options)) => hash(&options),
+        Ok(Command::Cavp(options)) => cavp::cavp(&options),
+        Ok(Command::Trace(options)) => trace::trace(&options),
+        Err(e) => {
+            eprintln!("sha_algorithm: {}", e);
+            eprintln!("Try 'sha_algorithm --help' for more information.");
+            EXIT_USAGE
+        },
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let (command, rest) = match args.split_first() {
+        Some((command, rest)) => (command.as_str(), rest),
+        None => return Ok(Command::Help),
+    };
+    match command {
+        "-h" | "--help" | "help" => Ok(Command::Help),
+        "hash" => parse_hash_args(rest),
+        "cavp" => parse_cavp_args(rest),
+        "trace" => parse_trace_args(rest),
+        _ => Err(format!("unknown command '{}'", command)),
+    }
+}
+
+fn parse_hash_args(args: &[String]) -> Result<Command, String> {
+    let mut options = HashOptions { algorithm: None, inputs: Vec::new(), binary: false, check: false, quiet: false };
+    let mut length = None;
+    let mut only_files = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if only_files {
+            options.inputs.push(file_input(arg));
+            continue;
+        }
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || inline_value.clone().or_else(|| args.next().cloned())
+            .ok_or_else(|| format!("option '{}' requires a value", flag));
+        match flag {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-a" | "--algo" => options.algorithm = Some(parse_algorithm(&value()?)?),
+            "-s" | "--string" => options.inputs.push(Input::Text(value()?)),
+            "-l" | "--length" => {
+                let bits = value()?;
+                length = Some(bits.parse::<u32>().map_err(|_| format!("invalid length '{}'", bits))?);
+            },
+            "-b" | "--binary" => options.binary = true,
+            "-t" | "--text" => options.binary = false,
+            "-c" | "--check" => options.check = true,
+            "--quiet" => options.quiet = true,
+            "--" => only_files = true,
+            "-" => options.inputs.push(Input::Stdin),
+            _ if flag.starts_with('-') => return Err(format!("unknown option '{}'", flag)),
+            _ => options.inputs.push(file_input(arg)),
+        }
+    }
+    if let Some(bits) = length {
+        let algorithm = match options.algorithm {
+            Some(ShaAlgorithm::SHAKE128(_)) => ShaAlgorithm::SHAKE128(bits),
+            Some(ShaAlgorithm::SHAKE256(_)) => ShaAlgorithm::SHAKE256(bits),
+            _ => return Err("--length only applies to shake128 and shake256".to_string()),
+        };
+        Hasher::new(&algorithm).map_err(|e| format!("{}: {}", algorithm, e))?;
+        options.algorithm = Some(algorithm);
+    }
+    if options.check && options.inputs.iter().any(|input| matches!(input, Input::Text(_))) {
+        return Err("--string can't be used with --check".to_string());
+    }
+    if options.inputs.is_empty() {
+        options.inputs.push(Input::Stdin);
+    }
+    Ok(Command::Hash(options))
+}
+
+fn parse_cavp_args(args: &[String]) -> Result<Command, String> {
+    let mut options = cavp::CavpOptions { algorithm: None, files: Vec::new(), quiet: false };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        match flag {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-a" | "--algo" => {
+                let name = inline_value.or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("option '{}' requires a value", flag))?;
+                options.algorithm = Some(parse_algorithm(&name)?);
+            },
+            "--quiet" => options.quiet = true,
+            _ if flag.starts_with('-') => return Err(format!("unknown option '{}'", flag)),
+            _ => options.files.push(arg.clone()),
+        }
+    }
+    if options.files.is_empty() {
+        return Err("cavp needs at least one .rsp file".to_string());
+    }
+    Ok(Command::Cavp(options))
+}
+
+fn parse_trace_args(args: &[String]) -> Result<Command, String> {
+    let mut algorithm = ShaAlgorithm::SHA256;
+    let mut format = trace::Format::Table;
+    let mut inputs = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || inline_value.clone().or_else(|| args.next().cloned())
+            .ok_or_else(|| format!("option '{}' requires a value", flag));
+        match flag {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-a" | "--algo" => algorithm = parse_algorithm(&value()?)?,
+            "-s" | "--string" => inputs.push(Input::Text(value()?)),
+            "-f" | "--format" => format = match value()?.as_str() {
+                "table" => trace::Format::Table,
+                "json" => trace::Format::Json,
+                "csv" => trace::Format::Csv,
+                other => return Err(format!("unknown format '{}'", other)),
+            },
+            "-" => inputs.push(Input::Stdin),
+            _ if flag.starts_with('-') => return Err(format!("unknown option '{}'", flag)),
+            _ => inputs.push(file_input(arg)),
+        }
+    }
+    let input = match inputs.len() {
+        0 => Input::Stdin,
+        1 => inputs.remove(0),
+        _ => return Err("trace takes a single input".to_string()),
+    };
+    Ok(Command::Trace(trace::TraceOptions { algorithm, input, format }))
+}
+
+fn file_input(path: &str) -> Input {
+    match path {
+        "-" => Input::Stdin,
+        _ => Input::File(path.to_string()),
+    }
+}
+
+fn parse_algorithm(name: &str) -> Result<ShaAlgorithm, String> {
+    let algorithm = name.parse::<ShaAlgorithm>().map_err(|e| match e {
+        ShaError::InvalidAlgorithm => format!("unknown algorithm '{}'", name),
+        e => format!("{}: {}", name, e),
+    })?;
+    // Rejects invalid SHA-512/t lengths before any input is read
+    Hasher::new(&algorithm).map_err(|e| format!("{}: {}", name, e))?;
+    Ok(algorithm)
+}
+
+fn hash(options: &HashOptions) -> i32 {
+    let algorithm = options.algorithm.unwrap_or(ShaAlgorithm::SHA256);
+    let mut status = EXIT_SUCCESS;
+    for input in options.inputs.iter() {
+        let (name, result) = hash_input(input, &algorithm);
+        match (input, result) {
+            (Input::Text(_), Ok(hash)) => println!("{}", hash.digest()),
+            (_, Ok(hash)) => println!("{}", checksum_line(&hash.digest(), &name, options.binary)),
+            (_, Err(e)) => {
+                eprintln!("sha_algorithm: {}: {}", name, e);
+                status = EXIT_FAILURE;
+            },
+        }
+    }
+    status
+}
+
+fn hash_input(input: &Input, algorithm: &ShaAlgorithm) -> (String, Result<HashResult, ShaError>) {
+    match input {
+        Input::Text(text) => (format!("\"{}\"", text), hasher::hash_bytes(text.as_bytes(), algorithm)),
+        Input::File(path) => (path.clone(), hasher::hash_file(path, algorithm)),
+        Input::Stdin => ("-".to_string(), hasher::hash_reader(std::io::stdin().lock(), algorithm)),
+    }
+}
+
+// Formats a line as sha256sum does, escaping file names that contain '\' or a newline
+fn checksum_line(digest: &Digest, name: &str, binary: bool) -> String {
+    let marker = if binary { '*' } else { ' ' };
+    if name.contains('\\') || name.contains('\n') {
+        let escaped = name.replace('\\', "\\\\").replace('\n', "\\n");
+        format!("\\{} {}{}", digest, marker, escaped)
+    } else {
+        format!("{} {}{}", digest, marker, name)
+    }
+}
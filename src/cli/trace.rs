@@ -0,0 +1,50 @@
+// Prints the round-by-round trace of a SHA-1 or SHA-2 computation
+
+use std::io::Read;
+use sha_algorithm::trace;
+use sha_algorithm::ShaAlgorithm;
+use super::{Input, EXIT_FAILURE, EXIT_SUCCESS};
+
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+pub struct TraceOptions {
+    pub algorithm: ShaAlgorithm,
+    pub input: Input,
+    pub format: Format,
+}
+
+pub fn trace(options: &TraceOptions) -> i32 {
+    let (name, msg) = match &options.input {
+        Input::Text(text) => (format!("\"{}\"", text), Ok(text.clone().into_bytes())),
+        Input::File(path) => (path.clone(), std::fs::read(path)),
+        Input::Stdin => {
+            let mut msg = Vec::new();
+            ("-".to_string(), std::io::stdin().lock().read_to_end(&mut msg).map(|_| msg))
+        },
+    };
+    let msg = match msg {
+        Ok(msg) => msg,
+        Err(e) => {
+            eprintln!("sha_algorithm: {}: {}", name, e);
+            return EXIT_FAILURE;
+        },
+    };
+    match trace::trace_message(&msg, &options.algorithm) {
+        Ok((hash, trace)) => {
+            match options.format {
+                Format::Table => println!("{}\n{} digest: {}", trace.to_table(), options.algorithm, hash.digest()),
+                Format::Json => println!("{}", trace.to_json()),
+                Format::Csv => print!("{}", trace.to_csv()),
+            }
+            EXIT_SUCCESS
+        },
+        Err(e) => {
+            eprintln!("sha_algorithm: {}: {}", options.algorithm, e);
+            EXIT_FAILURE
+        },
+    }
+}
@@ -0,0 +1,50 @@
+// Public API of the SHA library
+//
+// With the default `std` feature off the crate is `no_std` and allocation free,
+// leaving SHA-1, SHA-2 and the const fn hashes.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod sha_lib;
+
+pub use sha_lib::sha1;
+pub use sha_lib::sha2;
+#[cfg(feature = "std")]
+pub use sha_lib::sha3;
+pub use sha_lib::types;
+pub use sha_lib::pre_processing;
+pub use sha_lib::err_handling;
+pub use sha_lib::compare;
+#[cfg(feature = "std")]
+pub use sha_lib::hasher;
+#[cfg(feature = "std")]
+pub use sha_lib::hmac;
+#[cfg(feature = "std")]
+pub use sha_lib::hkdf;
+#[cfg(feature = "std")]
+pub use sha_lib::pbkdf2;
+#[cfg(feature = "std")]
+pub use sha_lib::sp800_185;
+#[cfg(feature = "std")]
+pub use sha_lib::cavp;
+#[cfg(feature = "std")]
+pub use sha_lib::trace;
+pub use sha_lib::const_hash;
+
+pub use sha_lib::types::HashResult;
+#[cfg(feature = "std")]
+pub use sha_lib::types::Digest;
+pub use sha_lib::types::{Sha1Digest, Sha224Digest, Sha256Digest, Sha384Digest, Sha512Digest, Sha512_224Digest, Sha512_256Digest};
+pub use sha_lib::types::{Sha3_224Digest, Sha3_256Digest, Sha3_384Digest, Sha3_512Digest};
+pub use sha_lib::types::wrappers::ShaAlgorithm;
+pub use sha_lib::err_handling::ShaError;
+pub use sha_lib::sha1::Sha1Hasher;
+pub use sha_lib::sha2::Sha2Hasher;
+#[cfg(feature = "std")]
+pub use sha_lib::sha3::{Sha3Hasher, ShakeHasher, XofReader};
+#[cfg(feature = "std")]
+pub use sha_lib::hasher::Hasher;
+#[cfg(feature = "std")]
+pub use sha_lib::hmac::Hmac;
+#[cfg(feature = "std")]
+pub use sha_lib::sp800_185::{CShake, Kmac, TupleHash, ParallelHash};
@@ -0,0 +1,305 @@
+// Runner for NIST CAVP response files (.rsp) of SHAVS and HMACVS
+//
+// Supports the SHA-1 and SHA-2 ShortMsg, LongMsg and Monte files, byte or bit
+// oriented, including SHA-512/224 and SHA-512/256, and the HMAC.rsp file.
+
+use std::fs;
+use std::path::Path;
+use crate::sha_lib::err_handling::ShaError;
+use crate::sha_lib::hasher::{self, Hasher};
+use crate::sha_lib::hmac;
+use crate::sha_lib::types::Digest;
+use crate::sha_lib::types::wrappers::ShaAlgorithm;
+
+/// One test vector of a response file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TestVector {
+    // The first `len` bits of `msg` hash to `md`
+    Hash { algorithm: ShaAlgorithm, len: u64, msg: Vec<u8>, md: Vec<u8> },
+    // Monte Carlo checkpoint: `MONTE_ITERATIONS` chained hashes starting from `seed` end in `md`
+    Monte { algorithm: ShaAlgorithm, count: u32, seed: Vec<u8>, md: Vec<u8> },
+    // HMAC of `msg` under `key`, truncated to the length of `mac`
+    Hmac { algorithm: ShaAlgorithm, count: u32, key: Vec<u8>, msg: Vec<u8>, mac: Vec<u8> },
+}
+
+impl TestVector {
+    // Recomputes the expected value; Ok(false) means a mismatch
+    pub fn run(&self) -> Result<bool, ShaError> {
+        match self {
+            TestVector::Hash { algorithm, len, msg, md } => {
+                let msg = &msg[..len.div_ceil(8) as usize];
+                let hash = if len.is_multiple_of(8) {
+                    hasher::hash_bytes(msg, algorithm)?
+                } else {
+                    hasher::hash_bits(msg, *len, algorithm)?
+                };
+                Ok(hash.get_values() == *md)
+            },
+            TestVector::Monte { algorithm, seed, md, .. } => Ok(monte_carlo_checkpoint(algorithm, seed)? == *md),
+            TestVector::Hmac { algorithm, key, msg, mac, .. } => {
+                let tag = hmac::hmac(algorithm, key, msg)?.get_values();
+                Ok(tag.get(..mac.len()) == Some(mac.as_slice()))
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for TestVector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TestVector::Hash { algorithm, len, .. } => write!(f, "{} Len = {}", algorithm, len),
+            TestVector::Monte { algorithm, count, .. } => write!(f, "{} Monte COUNT = {}", algorithm, count),
+            TestVector::Hmac { algorithm, count, .. } => write!(f, "HMAC-{} Count = {}", algorithm, count),
+        }
+    }
+}
+
+// Hashes chained between two Monte Carlo checkpoints, and checkpoints in a Monte file
+pub const MONTE_ITERATIONS: usize = 1000;
+pub const MONTE_CHECKPOINTS: usize = 100;
+
+/// Runs the SHAVS Monte Carlo procedure from `seed` up to the next checkpoint.
+///
+/// Each message is the concatenation of the last three digests, all three
+/// starting as the seed: MD[i] = SHA(MD[i-3] || MD[i-2] || MD[i-1]).
+pub fn monte_carlo_checkpoint(algorithm: &ShaAlgorithm, seed: &[u8]) -> Result<Vec<u8>, ShaError> {
+    if !is_fips_180(algorithm) {
+        return Err(ShaError::InvalidAlgorithm);
+    }
+    if seed.len() != algorithm.output_size() {
+        return Err(ShaError::InvalidOutputLength(seed.len()));
+    }
+    // Cloning a fresh hasher skips recomputing the SHA-512/t initial values
+    let fresh = Hasher::new(algorithm)?;
+    let mut md = [seed.to_vec(), seed.to_vec(), seed.to_vec()];
+    for _ in 0..MONTE_ITERATIONS {
+        let mut hasher = fresh.clone();
+        for digest in md.iter() {
+            hasher.update(digest)?;
+        }
+        md.rotate_left(1);
+        md[2] = hasher.finalize()?.get_values();
+    }
+    let [_, _, last] = md;
+    Ok(last)
+}
+
+/// Runs the whole Monte Carlo test, returning the digest of every checkpoint.
+pub fn monte_carlo(algorithm: &ShaAlgorithm, seed: &[u8], checkpoints: usize) -> Result<Vec<Vec<u8>>, ShaError> {
+    let mut results = Vec::with_capacity(checkpoints);
+    let mut seed = seed.to_vec();
+    for _ in 0..checkpoints {
+        seed = monte_carlo_checkpoint(algorithm, &seed)?;
+        results.push(seed.clone());
+    }
+    Ok(results)
+}
+
+/// A vector together with the line of the file where it starts.
+#[derive(Clone, Debug)]
+pub struct Entry {
+    pub line: usize,
+    pub vector: TestVector,
+}
+
+/// Outcome of running one entry.
+#[derive(Clone, Debug)]
+pub struct TestResult {
+    pub entry: Entry,
+    pub passed: bool,
+}
+
+// Fields of the vector being read, with the line of the first one
+#[derive(Default)]
+struct Record {
+    line: usize,
+    fields: Vec<(String, String)>,
+}
+
+impl Record {
+    fn get(&self, name: &str) -> Result<&str, ShaError> {
+        self.fields.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+            .ok_or_else(|| error(self.line, &format!("missing field '{}'", name)))
+    }
+
+    fn number(&self, name: &str) -> Result<u64, ShaError> {
+        let value = self.get(name)?;
+        value.parse().map_err(|_| error(self.line, &format!("invalid {} '{}'", name, value)))
+    }
+
+    fn bytes(&self, name: &str) -> Result<Vec<u8>, ShaError> {
+        let value = self.get(name)?;
+        value.parse::<Digest>()
+            .map(|digest| digest.as_bytes().to_vec())
+            .map_err(|_| error(self.line, &format!("invalid hexadecimal {}", name)))
+    }
+}
+
+fn error(line: usize, reason: &str) -> ShaError {
+    ShaError::CustomError(format!("line {}: {}", line, reason))
+}
+
+// The files name their algorithm in a comment such as `#  "SHA-512/224 ShortMsg" information`
+fn algorithm_from_header(comment: &str) -> Option<ShaAlgorithm> {
+    let quoted = comment.split('"').nth(1)?;
+    quoted.split_whitespace().next()?.parse().ok()
+}
+
+// File names such as SHA1ShortMsg.rsp or SHA512_224LongMsg.rsp
+fn algorithm_from_file_name(name: &str) -> Option<ShaAlgorithm> {
+    let stem = Path::new(name).file_stem()?.to_str()?;
+    let end = stem.find("ShortMsg").or_else(|| stem.find("LongMsg")).or_else(|| stem.find("Monte"))?;
+    stem[..end].replace('_', "/").parse().ok()
+}
+
+// `[L = 32]` gives the digest length in bytes
+fn algorithm_from_digest_length(len: usize) -> Option<ShaAlgorithm> {
+    match len {
+        20 => Some(ShaAlgorithm::SHA1),
+        28 => Some(ShaAlgorithm::SHA224),
+        32 => Some(ShaAlgorithm::SHA256),
+        48 => Some(ShaAlgorithm::SHA384),
+        64 => Some(ShaAlgorithm::SHA512),
+        _ => None,
+    }
+}
+
+fn is_fips_180(algorithm: &ShaAlgorithm) -> bool {
+    matches!(algorithm, ShaAlgorithm::SHA1 | ShaAlgorithm::SHA224 | ShaAlgorithm::SHA256
+        | ShaAlgorithm::SHA384 | ShaAlgorithm::SHA512 | ShaAlgorithm::SHA512T(_))
+}
+
+/// Parses the contents of a response file.
+///
+/// The hash algorithm is `algorithm` when given, otherwise the one named in the
+/// header comments, in `file_name` or, last, by the `[L = ...]` digest length.
+pub fn parse(text: &str, file_name: &str, algorithm: Option<ShaAlgorithm>) -> Result<Vec<Entry>, ShaError> {
+    let mut header_algorithm = None;
+    let mut digest_length = None;
+    let mut record = Record::default();
+    let mut entries = Vec::new();
+    // Seed of the next Monte Carlo checkpoint: the Seed field, then each expected MD
+    let mut monte_seed: Option<Vec<u8>> = None;
+
+    for (i, line) in text.lines().enumerate() {
+        let line_number = i + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            header_algorithm = header_algorithm.or_else(|| algorithm_from_header(comment));
+            continue;
+        }
+        if let Some(section) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            if let Some((name, value)) = section.split_once('=') {
+                if name.trim() == "L" {
+                    let value = value.trim();
+                    digest_length = Some(value.parse::<usize>().map_err(|_| error(line_number, &format!("invalid L '{}'", value)))?);
+                }
+            }
+            continue;
+        }
+        let (name, value) = line.split_once('=').ok_or_else(|| error(line_number, "expected 'name = value'"))?;
+        if name.trim() == "Seed" {
+            let value = value.trim();
+            monte_seed = Some(value.parse::<Digest>().map_err(|_| error(line_number, "invalid hexadecimal Seed"))?.as_bytes().to_vec());
+            continue;
+        }
+        if record.fields.is_empty() {
+            record.line = line_number;
+        }
+        record.fields.push((name.trim().to_string(), value.trim().to_string()));
+
+        let vector = match name.trim() {
+            "MD" => {
+                let algorithm = algorithm
+                    .or(header_algorithm)
+                    .or_else(|| algorithm_from_file_name(file_name))
+                    .or_else(|| digest_length.and_then(algorithm_from_digest_length))
+                    .ok_or_else(|| error(record.line, "can't tell the hash algorithm"))?;
+                let vector = match monte_seed.take() {
+                    Some(seed) => monte_vector(&record, algorithm, digest_length, seed)?,
+                    None => hash_vector(&record, algorithm, digest_length)?,
+                };
+                if let TestVector::Monte { md, .. } = &vector {
+                    monte_seed = Some(md.clone());
+                }
+                vector
+            },
+            "Mac" => hmac_vector(&record, digest_length)?,
+            _ => continue,
+        };
+        entries.push(Entry { line: record.line, vector });
+        record = Record::default();
+    }
+    if !record.fields.is_empty() {
+        return Err(error(record.line, "incomplete test vector"));
+    }
+    Ok(entries)
+}
+
+fn check_algorithm(record: &Record, algorithm: ShaAlgorithm, digest_length: Option<usize>) -> Result<(), ShaError> {
+    if !is_fips_180(&algorithm) {
+        return Err(error(record.line, &format!("{} vectors are not supported", algorithm)));
+    }
+    if digest_length.is_some_and(|len| len != algorithm.output_size()) {
+        return Err(error(record.line, &format!("L doesn't match the digest length of {}", algorithm)));
+    }
+    Ok(())
+}
+
+fn hash_vector(record: &Record, algorithm: ShaAlgorithm, digest_length: Option<usize>) -> Result<TestVector, ShaError> {
+    check_algorithm(record, algorithm, digest_length)?;
+    let len = record.number("Len")?;
+    let msg = record.bytes("Msg")?;
+    let md = record.bytes("MD")?;
+    // A zero length message is written as "Msg = 00"
+    if (msg.len() as u64) < len.div_ceil(8) {
+        return Err(error(record.line, "Msg is shorter than Len"));
+    }
+    Ok(TestVector::Hash { algorithm, len, msg, md })
+}
+
+fn monte_vector(record: &Record, algorithm: ShaAlgorithm, digest_length: Option<usize>, seed: Vec<u8>) -> Result<TestVector, ShaError> {
+    check_algorithm(record, algorithm, digest_length)?;
+    let md = record.bytes("MD")?;
+    if seed.len() != algorithm.output_size() || md.len() != algorithm.output_size() {
+        return Err(error(record.line, &format!("Seed or MD isn't a {} digest", algorithm)));
+    }
+    Ok(TestVector::Monte { algorithm, count: record.number("COUNT")? as u32, seed, md })
+}
+
+fn hmac_vector(record: &Record, digest_length: Option<usize>) -> Result<TestVector, ShaError> {
+    let algorithm = digest_length
+        .and_then(algorithm_from_digest_length)
+        .ok_or_else(|| error(record.line, "missing or invalid [L = ...] before HMAC vectors"))?;
+    let key = record.bytes("Key")?;
+    let mac = record.bytes("Mac")?;
+    if record.number("Klen")? != key.len() as u64 || record.number("Tlen")? != mac.len() as u64 {
+        return Err(error(record.line, "Klen or Tlen don't match Key or Mac"));
+    }
+    Ok(TestVector::Hmac {
+        algorithm,
+        count: record.number("Count")? as u32,
+        key,
+        msg: record.bytes("Msg")?,
+        mac,
+    })
+}
+
+/// Runs every entry, in file order.
+pub fn run(entries: &[Entry]) -> Vec<TestResult> {
+    entries.iter()
+        .map(|entry| TestResult { entry: entry.clone(), passed: matches!(entry.vector.run(), Ok(true)) })
+        .collect()
+}
+
+/// Parses and runs the response file at `path`.
+pub fn run_file<P: AsRef<Path>>(path: P, algorithm: Option<ShaAlgorithm>) -> Result<Vec<TestResult>, ShaError> {
+    let text = fs::read_to_string(&path)?;
+    let entries = parse(&text, &path.as_ref().to_string_lossy(), algorithm)?;
+    Ok(run(&entries))
+}
@@ -0,0 +1,38 @@
+// Comparisons of digests and MACs whose running time does not depend on where
+// the inputs differ
+
+use crate::sha_lib::err_handling::ShaError;
+
+/// Compares two byte strings without stopping at the first difference.
+///
+/// Only the lengths, which are public for MACs, affect the running time.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff: u8 = 0;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    // Hides the accumulated value so the loop cannot be turned into an early exit
+    core::hint::black_box(diff) == 0
+}
+
+// Result of a MAC's `verify`; a tag of another length than the MAC never matches
+#[cfg(feature = "std")]
+pub(crate) fn verify_mac(computed: &[u8], tag: &[u8]) -> Result<(), ShaError> {
+    if constant_time_eq(computed, tag) {
+        Ok(())
+    } else {
+        Err(ShaError::InvalidMac)
+    }
+}
+
+// Result of a hasher's `verify`
+pub(crate) fn verify_digest(computed: &[u8], expected: &[u8]) -> Result<(), ShaError> {
+    if constant_time_eq(computed, expected) {
+        Ok(())
+    } else {
+        Err(ShaError::DigestMismatch)
+    }
+}
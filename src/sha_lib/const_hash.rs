@@ -0,0 +1,215 @@
+// SHA-1, SHA-256 and SHA-512 as `const fn`, so digests of data known at compile
+// time can be embedded in the binary:
+//
+//     const DIGEST: [u8; 32] = const_hash::sha256(b"config");
+//
+// They share the constants of the runtime implementation but are written with
+// plain loops and no allocation, as const evaluation requires. At runtime the
+// regular hashers are faster.
+
+use crate::sha_lib::constants::INITIAL_VALUES::{SHA1_INITIAL_VALUES, SHA256_INITIAL_VALUES, SHA512_INITIAL_VALUES};
+use crate::sha_lib::constants::SHA_CONSTANTS::{SHA1_K, SHA256_K, SHA512_K};
+
+// Number of blocks of the padded message: the data, the 0x80 byte and the length field
+const fn block_count(len: usize, block_size: usize, length_size: usize) -> usize {
+    (len + 1 + length_size).div_ceil(block_size)
+}
+
+// Block `index` of the padded message, built without materialising the padding
+const fn padded_block<const B: usize, const L: usize>(msg: &[u8], index: usize) -> [u8; B] {
+    let mut block = [0u8; B];
+    let start = index * B;
+    let mut i = 0;
+    while i < B {
+        let pos = start + i;
+        if pos < msg.len() {
+            block[i] = msg[pos];
+        } else if pos == msg.len() {
+            block[i] = 0x80;
+        }
+        i += 1;
+    }
+    if index == block_count(msg.len(), B, L) - 1 {
+        let bit_len = (msg.len() as u128).wrapping_mul(8).to_be_bytes();
+        let mut i = 0;
+        while i < L {
+            block[B - L + i] = bit_len[16 - L + i];
+            i += 1;
+        }
+    }
+    block
+}
+
+/// SHA-1 of `msg`, usable in constant expressions.
+#[allow(non_snake_case)]
+pub const fn sha1(msg: &[u8]) -> [u8; 20] {
+    let mut H = SHA1_INITIAL_VALUES;
+    let blocks = block_count(msg.len(), 64, 8);
+    let mut n = 0;
+    while n < blocks {
+        let block = padded_block::<64, 8>(msg, n);
+        let mut W = [0u32; 80];
+        let mut t = 0;
+        while t < 80 {
+            W[t] = if t < 16 {
+                u32::from_be_bytes([block[4 * t], block[4 * t + 1], block[4 * t + 2], block[4 * t + 3]])
+            } else {
+                (W[t - 3] ^ W[t - 8] ^ W[t - 14] ^ W[t - 16]).rotate_left(1)
+            };
+            t += 1;
+        }
+
+        let (mut a, mut b, mut c, mut d, mut e) = (H[0], H[1], H[2], H[3], H[4]);
+        let mut t = 0;
+        while t < 80 {
+            let f = match t {
+                0..=19 => (b & c) ^ (!b & d),
+                40..=59 => (b & c) ^ (b & d) ^ (c & d),
+                _ => b ^ c ^ d,
+            };
+            let T = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(SHA1_K[t / 20]).wrapping_add(W[t]);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = T;
+            t += 1;
+        }
+        H[0] = H[0].wrapping_add(a);
+        H[1] = H[1].wrapping_add(b);
+        H[2] = H[2].wrapping_add(c);
+        H[3] = H[3].wrapping_add(d);
+        H[4] = H[4].wrapping_add(e);
+        n += 1;
+    }
+
+    let mut digest = [0u8; 20];
+    let mut i = 0;
+    while i < 20 {
+        digest[i] = H[i / 4].to_be_bytes()[i % 4];
+        i += 1;
+    }
+    digest
+}
+
+/// SHA-256 of `msg`, usable in constant expressions.
+#[allow(non_snake_case)]
+pub const fn sha256(msg: &[u8]) -> [u8; 32] {
+    let mut H = SHA256_INITIAL_VALUES;
+    let blocks = block_count(msg.len(), 64, 8);
+    let mut n = 0;
+    while n < blocks {
+        let block = padded_block::<64, 8>(msg, n);
+        let mut W = [0u32; 64];
+        let mut t = 0;
+        while t < 64 {
+            W[t] = if t < 16 {
+                u32::from_be_bytes([block[4 * t], block[4 * t + 1], block[4 * t + 2], block[4 * t + 3]])
+            } else {
+                let s0 = W[t - 15].rotate_right(7) ^ W[t - 15].rotate_right(18) ^ (W[t - 15] >> 3);
+                let s1 = W[t - 2].rotate_right(17) ^ W[t - 2].rotate_right(19) ^ (W[t - 2] >> 10);
+                s1.wrapping_add(W[t - 7]).wrapping_add(s0).wrapping_add(W[t - 16])
+            };
+            t += 1;
+        }
+
+        let (mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h) = (H[0], H[1], H[2], H[3], H[4], H[5], H[6], H[7]);
+        let mut t = 0;
+        while t < 64 {
+            let S1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let T1 = h.wrapping_add(S1).wrapping_add(ch).wrapping_add(SHA256_K[t]).wrapping_add(W[t]);
+            let S0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let T2 = S0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(T1);
+            d = c;
+            c = b;
+            b = a;
+            a = T1.wrapping_add(T2);
+            t += 1;
+        }
+        let working = [a, b, c, d, e, f, g, h];
+        let mut i = 0;
+        while i < 8 {
+            H[i] = H[i].wrapping_add(working[i]);
+            i += 1;
+        }
+        n += 1;
+    }
+
+    let mut digest = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        digest[i] = H[i / 4].to_be_bytes()[i % 4];
+        i += 1;
+    }
+    digest
+}
+
+/// SHA-512 of `msg`, usable in constant expressions.
+#[allow(non_snake_case)]
+pub const fn sha512(msg: &[u8]) -> [u8; 64] {
+    let mut H = SHA512_INITIAL_VALUES;
+    let blocks = block_count(msg.len(), 128, 16);
+    let mut n = 0;
+    while n < blocks {
+        let block = padded_block::<128, 16>(msg, n);
+        let mut W = [0u64; 80];
+        let mut t = 0;
+        while t < 80 {
+            W[t] = if t < 16 {
+                let mut word = [0u8; 8];
+                let mut i = 0;
+                while i < 8 {
+                    word[i] = block[8 * t + i];
+                    i += 1;
+                }
+                u64::from_be_bytes(word)
+            } else {
+                let s0 = W[t - 15].rotate_right(1) ^ W[t - 15].rotate_right(8) ^ (W[t - 15] >> 7);
+                let s1 = W[t - 2].rotate_right(19) ^ W[t - 2].rotate_right(61) ^ (W[t - 2] >> 6);
+                s1.wrapping_add(W[t - 7]).wrapping_add(s0).wrapping_add(W[t - 16])
+            };
+            t += 1;
+        }
+
+        let (mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h) = (H[0], H[1], H[2], H[3], H[4], H[5], H[6], H[7]);
+        let mut t = 0;
+        while t < 80 {
+            let S1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+            let ch = (e & f) ^ (!e & g);
+            let T1 = h.wrapping_add(S1).wrapping_add(ch).wrapping_add(SHA512_K[t]).wrapping_add(W[t]);
+            let S0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let T2 = S0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(T1);
+            d = c;
+            c = b;
+            b = a;
+            a = T1.wrapping_add(T2);
+            t += 1;
+        }
+        let working = [a, b, c, d, e, f, g, h];
+        let mut i = 0;
+        while i < 8 {
+            H[i] = H[i].wrapping_add(working[i]);
+            i += 1;
+        }
+        n += 1;
+    }
+
+    let mut digest = [0u8; 64];
+    let mut i = 0;
+    while i < 64 {
+        digest[i] = H[i / 8].to_be_bytes()[i % 8];
+        i += 1;
+    }
+    digest
+}
@@ -2,19 +2,6 @@
 #![allow(non_snake_case)]
 pub mod INITIAL_VALUES {
 
-    pub enum InitialValues {
-        Small([u32; 8]),
-        Large([u64; 8]),
-    }
-
-    pub enum Constants {
-        Small([u32; 64]),
-        Large([u64; 80]),
-        
-    }
-
-
-
     pub const SHA1_INITIAL_VALUES: [u32; 5] = [
         0x67452301,
         0xEFCDAB89,
@@ -77,7 +64,6 @@ pub mod SHA_CONSTANTS {
         0xCA62C1D6,
     ];
 
-    pub const SHA224_K: [u32; 64] = SHA256_K;
     pub const SHA256_K: [u32; 64] = [
         0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
         0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
@@ -89,7 +75,6 @@ pub mod SHA_CONSTANTS {
         0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
     ];
 
-    pub const SHA384_K: [u64; 80] = SHA512_K;
     pub const SHA512_K: [u64; 80] = [
         0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc,
         0x3956c25bf348b538, 0x59f111f1b605d019, 0x923f82a4af194f9b, 0xab1c5ed5da6d8118,
@@ -112,4 +97,37 @@ pub mod SHA_CONSTANTS {
         0x28db77f523047d84, 0x32caab7b40c72493, 0x3c9ebe0a15c9bebc, 0x431d67c49c100d4c,
         0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817,
     ];
-}
\ No newline at end of file
+}
+pub mod SHA3_CONSTANTS {
+
+    // Round constants of the iota step
+    #[cfg(feature = "std")]
+    pub const KECCAK_RC: [u64; 24] = [
+        0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+        0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+        0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+        0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+        0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+        0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+    ];
+
+    // Rotation offsets of the rho step, indexed by x + 5 * y
+    #[cfg(feature = "std")]
+    pub const KECCAK_RHO: [u32; 25] = [
+         0,  1, 62, 28, 27,
+        36, 44,  6, 55, 20,
+         3, 10, 43, 25, 39,
+        41, 45, 15, 21,  8,
+        18,  2, 61, 56, 14,
+    ];
+
+    // Rates in bytes of the SHA-3 hash functions
+    pub const SHA3_224_RATE: usize = 144;
+    pub const SHA3_256_RATE: usize = 136;
+    pub const SHA3_384_RATE: usize = 104;
+    pub const SHA3_512_RATE: usize = 72;
+
+    // Rates in bytes of the SHAKE extendable-output functions
+    pub const SHAKE128_RATE: usize = 168;
+    pub const SHAKE256_RATE: usize = 136;
+}
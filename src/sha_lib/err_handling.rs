@@ -7,12 +7,38 @@ pub enum ShaError {
     InvalidAlgorithm,
     #[error("Invalid padding")]
     InvalidPadding,
-    #[error("Invalid initial values")]
-    InvalidInitialValues,
-    #[error("Invalid constants")]
-    InvalidConstants,
     #[error("Invalid result")]
     InvalidResult,
-    #[error("Error")]
+    #[error("Invalid hexadecimal digest")]
+    InvalidDigest,
+    #[error("MAC verification failed")]
+    InvalidMac,
+    #[error("Digest verification failed")]
+    DigestMismatch,
+    #[error("Invalid digest length: {0} bytes")]
+    InvalidDigestLength(usize),
+    #[error("Invalid output length: {0} bytes")]
+    InvalidOutputLength(usize),
+    #[error("Invalid sponge rate: {0} bytes")]
+    InvalidRate(usize),
+    #[error("Invalid block size: {0} bytes")]
+    InvalidBlockSize(usize),
+    #[error("Invalid message length: {0} bits")]
+    InvalidMessageLength(u64),
+    #[error("Invalid length for SHA-512/{0}; must be a multiple of 8 from 8 to 504, other than 384")]
+    InvalidTruncation(u16),
+    #[error("Invalid length for SHAKE: {0} bits; must be a positive multiple of 8")]
+    InvalidShakeLength(u32),
+    #[error("Invalid value for t: {0}")]
+    InvalidRound(u8),
+    #[error("Invalid iteration count")]
+    InvalidIterationCount,
+    #[error("Invalid hasher state: {0}")]
+    InvalidState(&'static str),
+    #[cfg(feature = "std")]
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[cfg(feature = "std")]
+    #[error("{0}")]
     CustomError(String),
-}
\ No newline at end of file
+}
@@ -0,0 +1,132 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use crate::sha_lib::err_handling::ShaError;
+use crate::sha_lib::types::HashResult;
+use crate::sha_lib::types::wrappers::ShaAlgorithm;
+use crate::sha_lib::sha1::{self, Sha1Hasher};
+use crate::sha_lib::sha2::{self, Sha2Hasher};
+use crate::sha_lib::sha3::{self, Sha3Hasher, ShakeHasher};
+use crate::sha_lib::state;
+
+// Size of the chunks read from files and streams
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Streaming hasher for any `ShaAlgorithm`, chosen at runtime.
+#[derive(Clone, Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum Hasher {
+    Sha1(Sha1Hasher),
+    Sha2(Sha2Hasher),
+    Sha3(Sha3Hasher),
+    Shake(ShakeHasher),
+}
+
+impl Hasher {
+    pub fn new(algorithm: &ShaAlgorithm) -> Result<Hasher, ShaError> {
+        match algorithm {
+            ShaAlgorithm::SHA1 => Ok(Hasher::Sha1(Sha1Hasher::new())),
+            ShaAlgorithm::SHA3_224 | ShaAlgorithm::SHA3_256 | ShaAlgorithm::SHA3_384 | ShaAlgorithm::SHA3_512 => {
+                Ok(Hasher::Sha3(Sha3Hasher::new(algorithm)?))
+            },
+            ShaAlgorithm::SHAKE128(_) | ShaAlgorithm::SHAKE256(_) => Ok(Hasher::Shake(ShakeHasher::new(algorithm)?)),
+            _ => Ok(Hasher::Sha2(Sha2Hasher::new(algorithm)?)),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> Result<(), ShaError> {
+        match self {
+            Hasher::Sha1(hasher) => hasher.update(data),
+            Hasher::Sha2(hasher) => hasher.update(data),
+            Hasher::Sha3(hasher) => hasher.update(data),
+            Hasher::Shake(hasher) => hasher.update(data),
+        }
+    }
+
+    // Bit-granular input is only defined for the FIPS 180 hashes
+    pub fn update_bits(&mut self, data: &[u8], bit_len: u64) -> Result<(), ShaError> {
+        match self {
+            Hasher::Sha1(hasher) => hasher.update_bits(data, bit_len),
+            Hasher::Sha2(hasher) => hasher.update_bits(data, bit_len),
+            Hasher::Sha3(_) | Hasher::Shake(_) => Err(ShaError::InvalidAlgorithm),
+        }
+    }
+
+    // Only the SHA-1 and SHA-2 states can be exported
+    pub fn export_state(&self) -> Result<Vec<u8>, ShaError> {
+        match self {
+            Hasher::Sha1(hasher) => hasher.export_state(),
+            Hasher::Sha2(hasher) => hasher.export_state(),
+            Hasher::Sha3(_) | Hasher::Shake(_) => Err(ShaError::InvalidAlgorithm),
+        }
+    }
+
+    // Resumes a SHA-1 or SHA-2 hash from `export_state`, for whichever algorithm it was made with
+    pub fn import_state(blob: &[u8]) -> Result<Hasher, ShaError> {
+        let (algorithm, chaining_value, buffer) = state::decode(blob)?;
+        match algorithm {
+            ShaAlgorithm::SHA1 => Ok(Hasher::Sha1(Sha1Hasher::from_state(algorithm, chaining_value, buffer)?)),
+            _ => Ok(Hasher::Sha2(Sha2Hasher::from_state(algorithm, chaining_value, buffer)?)),
+        }
+    }
+
+    pub fn finalize(self) -> Result<HashResult, ShaError> {
+        match self {
+            Hasher::Sha1(hasher) => hasher.finalize(),
+            Hasher::Sha2(hasher) => hasher.finalize(),
+            Hasher::Sha3(hasher) => hasher.finalize(),
+            Hasher::Shake(hasher) => hasher.finalize(),
+        }
+    }
+
+    // Checks the digest against `expected` in constant time
+    pub fn verify(self, expected: &[u8]) -> Result<(), ShaError> {
+        match self {
+            Hasher::Sha1(hasher) => hasher.verify(expected),
+            Hasher::Sha2(hasher) => hasher.verify(expected),
+            Hasher::Sha3(hasher) => hasher.verify(expected),
+            Hasher::Shake(hasher) => hasher.verify(expected),
+        }
+    }
+}
+
+/// Hashes `msg` in one go with any `ShaAlgorithm`.
+pub fn hash_bytes(msg: &[u8], algorithm: &ShaAlgorithm) -> Result<HashResult, ShaError> {
+    match algorithm {
+        ShaAlgorithm::SHA1 => sha1::hash_message_bytes(msg, algorithm),
+        ShaAlgorithm::SHA3_224 | ShaAlgorithm::SHA3_256 | ShaAlgorithm::SHA3_384 | ShaAlgorithm::SHA3_512 => {
+            sha3::hash_message_bytes(msg, algorithm)
+        },
+        ShaAlgorithm::SHAKE128(_) | ShaAlgorithm::SHAKE256(_) => sha3::shake(msg, algorithm),
+        _ => sha2::hash_message_bytes(msg, algorithm),
+    }
+}
+
+/// Hashes the first `bit_len` bits of `msg` with SHA-1 or SHA-2.
+pub fn hash_bits(msg: &[u8], bit_len: u64, algorithm: &ShaAlgorithm) -> Result<HashResult, ShaError> {
+    match algorithm {
+        ShaAlgorithm::SHA1 => sha1::hash_message_bits(msg, bit_len, algorithm),
+        _ => sha2::hash_message_bits(msg, bit_len, algorithm),
+    }
+}
+
+/// Hashes everything `reader` yields, `CHUNK_SIZE` bytes at a time.
+pub fn hash_reader<R: Read>(mut reader: R, algorithm: &ShaAlgorithm) -> Result<HashResult, ShaError> {
+    let mut hasher = Hasher::new(algorithm)?;
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => Err(e)?,
+        };
+        hasher.update(&buf[..n])?;
+    }
+    hasher.finalize()
+}
+
+/// Hashes the contents of the file at `path` without loading it into memory.
+pub fn hash_file<P: AsRef<Path>>(path: P, algorithm: &ShaAlgorithm) -> Result<HashResult, ShaError> {
+    hash_reader(File::open(path)?, algorithm)
+}
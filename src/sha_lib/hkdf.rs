@@ -0,0 +1,47 @@
+// HKDF extract-and-expand key derivation as defined in RFC 5869
+
+use crate::sha_lib::err_handling::ShaError;
+use crate::sha_lib::hmac::{self, Hmac};
+use crate::sha_lib::types::wrappers::ShaAlgorithm;
+use crate::sha_lib::zeroize::Zeroizing;
+
+/// HKDF-Extract: turns the input keying material into a pseudorandom key.
+///
+/// An empty `salt` stands for the RFC default of `HashLen` zero bytes, which gives
+/// the same HMAC key.
+pub fn extract(algorithm: &ShaAlgorithm, salt: &[u8], ikm: &[u8]) -> Result<Vec<u8>, ShaError> {
+    Ok(hmac::hmac(algorithm, salt, ikm)?.get_values())
+}
+
+/// HKDF-Expand: derives `length` bytes of output keying material from `prk`.
+///
+/// `length` can't exceed `255 * HashLen`.
+pub fn expand(algorithm: &ShaAlgorithm, prk: &[u8], info: &[u8], length: usize) -> Result<Vec<u8>, ShaError> {
+    let hash_len = algorithm.output_size();
+    if length > 255 * hash_len {
+        return Err(ShaError::InvalidOutputLength(length));
+    }
+
+    // T(i) = HMAC(PRK, T(i-1) | info | i), keyed only once
+    let keyed = Hmac::new(algorithm, prk)?;
+    let mut okm = Vec::with_capacity(length);
+    let mut previous = Zeroizing(Vec::new());
+    let mut counter: u8 = 1;
+    while okm.len() < length {
+        let mut mac = keyed.clone();
+        mac.update(&previous)?;
+        mac.update(info)?;
+        mac.update(&[counter])?;
+        previous = Zeroizing(mac.finalize()?.get_values());
+        let take = previous.len().min(length - okm.len());
+        okm.extend_from_slice(&previous[..take]);
+        counter = counter.wrapping_add(1);
+    }
+    Ok(okm)
+}
+
+/// Runs HKDF-Extract followed by HKDF-Expand.
+pub fn hkdf(algorithm: &ShaAlgorithm, salt: &[u8], ikm: &[u8], info: &[u8], length: usize) -> Result<Vec<u8>, ShaError> {
+    let prk = Zeroizing(extract(algorithm, salt, ikm)?);
+    expand(algorithm, &prk, info, length)
+}
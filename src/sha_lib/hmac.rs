@@ -0,0 +1,70 @@
+// HMAC as defined in RFC 2104 and FIPS 198-1, over the SHA-1, SHA-2 and SHA-3 hashes
+
+use crate::sha_lib::err_handling::ShaError;
+pub use crate::sha_lib::compare::constant_time_eq;
+use crate::sha_lib::compare::verify_mac;
+use crate::sha_lib::hasher::{self, Hasher};
+use crate::sha_lib::types::HashResult;
+use crate::sha_lib::types::wrappers::ShaAlgorithm;
+use crate::sha_lib::zeroize::Zeroizing;
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+/// Computes the HMAC of `msg` under `key` in one go.
+pub fn hmac(algorithm: &ShaAlgorithm, key: &[u8], msg: &[u8]) -> Result<HashResult, ShaError> {
+    let mut mac = Hmac::new(algorithm, key)?;
+    mac.update(msg)?;
+    mac.finalize()
+}
+
+/// Incremental HMAC.
+///
+/// The inner and outer hashers already hold the padded key when `new` returns,
+/// so a keyed `Hmac` can be cloned to authenticate several messages. Those
+/// hashers wipe their state when dropped, as do the key temporaries of `new`.
+#[derive(Clone, Debug)]
+pub struct Hmac {
+    inner: Hasher,
+    outer: Hasher,
+}
+
+impl Hmac {
+    pub fn new(algorithm: &ShaAlgorithm, key: &[u8]) -> Result<Hmac, ShaError> {
+        // HMAC is defined over hash functions only; SHA-3 XOFs are keyed with KMAC instead
+        if let ShaAlgorithm::SHAKE128(_) | ShaAlgorithm::SHAKE256(_) = algorithm {
+            return Err(ShaError::InvalidAlgorithm);
+        }
+
+        // Keys longer than a block are hashed first, shorter ones are padded with zeros
+        let block_size = algorithm.block_size();
+        let mut padded_key = Zeroizing(vec![0; block_size]);
+        if key.len() > block_size {
+            let hashed_key = Zeroizing(hasher::hash_bytes(key, algorithm)?.get_values());
+            padded_key[..hashed_key.len()].copy_from_slice(&hashed_key);
+        } else {
+            padded_key[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = Hasher::new(algorithm)?;
+        let mut outer = Hasher::new(algorithm)?;
+        inner.update(&Zeroizing(padded_key.iter().map(|b| b ^ IPAD).collect::<Vec<u8>>()))?;
+        outer.update(&Zeroizing(padded_key.iter().map(|b| b ^ OPAD).collect::<Vec<u8>>()))?;
+        Ok(Hmac { inner, outer })
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> Result<(), ShaError> {
+        self.inner.update(data)
+    }
+
+    pub fn finalize(self) -> Result<HashResult, ShaError> {
+        let mut outer = self.outer;
+        outer.update(&Zeroizing(self.inner.finalize()?.get_values()))?;
+        outer.finalize()
+    }
+
+    /// Checks `tag` against the computed MAC in constant time.
+    pub fn verify(self, tag: &[u8]) -> Result<(), ShaError> {
+        verify_mac(&self.finalize()?.get_values(), tag)
+    }
+}
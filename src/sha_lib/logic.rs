@@ -2,9 +2,9 @@ pub mod operations {
         
     pub fn ch<T: 
     Copy + 
-    std::ops::BitAnd<Output = T> + 
-    std::ops::Not<Output = T> + 
-    std::ops::BitXor<Output = T>
+    core::ops::BitAnd<Output = T> + 
+    core::ops::Not<Output = T> + 
+    core::ops::BitXor<Output = T>
     >
     (x: T, y: T, z: T) -> T {
         (x & y) ^ (!x & z)
@@ -12,8 +12,8 @@ pub mod operations {
 
     pub fn maj<T:
         Copy + 
-        std::ops::BitAnd<Output = T> + 
-        std::ops::BitXor<Output = T>
+        core::ops::BitAnd<Output = T> + 
+        core::ops::BitXor<Output = T>
         >
     (x: T, y: T, z: T) -> T {
         (x & y) ^ (x & z) ^ (y & z)
@@ -21,7 +21,7 @@ pub mod operations {
 
     pub fn parity<T:
         Copy +
-        std::ops::BitXor<Output = T>
+        core::ops::BitXor<Output = T>
         >
     (x: T, y: T, z:T) -> T {
         x ^ y ^ z
@@ -29,7 +29,7 @@ pub mod operations {
 
     pub fn shr<T:
         Copy + 
-        std::ops::Shr<usize, Output = T>
+        core::ops::Shr<usize, Output = T>
         >
     (x: T, n: usize) -> T {
         x >> n
@@ -37,111 +37,109 @@ pub mod operations {
 
     pub fn rot_r<T:
         Copy +
-        std::ops::BitOr<Output = T> + 
-        std::ops::Shr<usize, Output = T> +
-        std::ops::Shl<usize, Output = T>
+        core::ops::BitOr<Output = T> + 
+        core::ops::Shr<usize, Output = T> +
+        core::ops::Shl<usize, Output = T>
         >
     (x: T, n: usize) -> T {
-        (x >> n) | (x << check_size::<T>() - n)
+        (x >> n) | (x << (check_size::<T>() - n))
     }
 
     pub fn rot_l<T:
         Copy +
-        std::ops::BitOr<Output = T> + 
-        std::ops::Shr<usize, Output = T> +
-        std::ops::Shl<usize, Output = T>
+        core::ops::BitOr<Output = T> + 
+        core::ops::Shr<usize, Output = T> +
+        core::ops::Shl<usize, Output = T>
         >
     (x: T, n: usize) -> T {
-        (x << n) | (x >> check_size::<T>() - n)
+        (x << n) | (x >> (check_size::<T>() - n))
     }
 
     pub fn check_size<T>() -> usize {
-        std::mem::size_of::<T>() * 8 // Convertir de bytes a bits
+        core::mem::size_of::<T>() * 8 // Convertir de bytes a bits
     }
 
 }
 
+// Word types of SHA-2, carrying the shift and rotation amounts of FIPS 180-4
+// section 4.1.2 (32-bit) and 4.1.3 (64-bit) so the functions below resolve them at compile time
+pub mod word {
+
+    mod sealed {
+        pub trait Sealed {}
+        impl Sealed for u32 {}
+        impl Sealed for u64 {}
+    }
+
+    pub trait Word:
+        sealed::Sealed +
+        Copy +
+        core::ops::BitOr<Output = Self> +
+        core::ops::BitXor<Output = Self> +
+        core::ops::Shr<usize, Output = Self> +
+        core::ops::Shl<usize, Output = Self>
+    {
+        // ROTR amounts of the Σ functions
+        const CSIGMA_0: [usize; 3];
+        const CSIGMA_1: [usize; 3];
+        // ROTR, ROTR and SHR amounts of the σ functions
+        const SIGMA_0: [usize; 3];
+        const SIGMA_1: [usize; 3];
+    }
+
+    impl Word for u32 {
+        const CSIGMA_0: [usize; 3] = [2, 13, 22];
+        const CSIGMA_1: [usize; 3] = [6, 11, 25];
+        const SIGMA_0: [usize; 3] = [7, 18, 3];
+        const SIGMA_1: [usize; 3] = [17, 19, 10];
+    }
+
+    impl Word for u64 {
+        const CSIGMA_0: [usize; 3] = [28, 34, 39];
+        const CSIGMA_1: [usize; 3] = [14, 18, 41];
+        const SIGMA_0: [usize; 3] = [1, 8, 7];
+        const SIGMA_1: [usize; 3] = [19, 61, 6];
+    }
+}
+
 pub mod functions { 
 
     use super::operations::{rot_r, shr};
+    use super::word::Word;
 
-    pub fn f<T: Copy + std::ops::BitXor<Output = T> + std::ops::BitAnd<Output = T> + std::ops::Not<Output = T>
+    pub fn f<T: Copy + core::ops::BitXor<Output = T> + core::ops::BitAnd<Output = T> + core::ops::Not<Output = T>
     >(t: u8, x: T, y: T, z: T) -> Result<T, crate::err_handling::ShaError> {
         let ret = match t {
             0..=19 => super::operations::ch(x, y, z),
             20..=39 => super::operations::parity(x, y, z),
             40..=59 => super::operations::maj(x, y, z),
             60..=79 => super::operations::parity(x, y, z),
-            _ => Err(crate::err_handling::ShaError::CustomError("Invalid value for t".to_string()))?,
+            _ => Err(crate::err_handling::ShaError::InvalidRound(t))?,
         };
         Ok(ret)
     }
-    
-    pub fn csigma_0<T:
-        'static +
-        Copy +
-        std::ops::BitOr<Output = T> + 
-        std::ops::Shr<usize, Output = T> +
-        std::ops::Shl<usize, Output = T> +
-        std::ops::BitXor< Output = T>
-        >
-    (x: T) -> Result<T, crate::err_handling::ShaError> {
-        let type_id = std::any::TypeId::of::<T>();
-        match type_id {
-            t if t == std::any::TypeId::of::<u32>() => Ok(rot_r(x,2) ^ rot_r(x,13) ^ rot_r(x,22)),
-            t if t == std::any::TypeId::of::<u64>() => Ok(rot_r(x,28) ^ rot_r(x,34) ^ rot_r(x,39)),
-            _ => Err(crate::err_handling::ShaError::CustomError("Invalid integer type".to_string()))
-        }
+
+    #[inline]
+    pub fn csigma_0<T: Word>(x: T) -> T {
+        let [r1, r2, r3] = T::CSIGMA_0;
+        rot_r(x, r1) ^ rot_r(x, r2) ^ rot_r(x, r3)
     }
 
-    pub fn csigma_1<T:
-        'static +
-        Copy +
-        std::ops::BitOr<Output = T> + 
-        std::ops::Shr<usize, Output = T> +
-        std::ops::Shl<usize, Output = T> +
-        std::ops::BitXor< Output = T>
-        >
-    (x: T) -> Result<T, crate::sha_lib::err_handling::ShaError> {
-        let type_id = std::any::TypeId::of::<T>();
-        match type_id {
-            t if t == std::any::TypeId::of::<u32>() => Ok(rot_r(x,6) ^ rot_r(x,11) ^ rot_r(x,25)),
-            t if t == std::any::TypeId::of::<u64>() => Ok(rot_r(x,14) ^ rot_r(x,18) ^ rot_r(x,41)),
-            _ => Err(crate::err_handling::ShaError::CustomError("Invalid integer type".to_string()))
-        }
+    #[inline]
+    pub fn csigma_1<T: Word>(x: T) -> T {
+        let [r1, r2, r3] = T::CSIGMA_1;
+        rot_r(x, r1) ^ rot_r(x, r2) ^ rot_r(x, r3)
     }
 
-    pub fn sigma_0<T:
-        'static +
-        Copy +
-        std::ops::BitOr<Output = T> + 
-        std::ops::Shr<usize, Output = T> +
-        std::ops::Shl<usize, Output = T> +
-        std::ops::BitXor< Output = T>
-        >
-    (x: T) -> Result<T, crate::sha_lib::err_handling::ShaError> {
-        let type_id = std::any::TypeId::of::<T>();
-        match type_id {
-            t if t == std::any::TypeId::of::<u32>() => Ok(rot_r(x,7) ^ rot_r(x,18) ^ shr(x,3)),
-            t if t == std::any::TypeId::of::<u64>() => Ok(rot_r(x,1) ^ rot_r(x,8) ^ shr(x,7)),
-            _ => Err(crate::err_handling::ShaError::CustomError("Invalid integer type".to_string()))
-        }
+    #[inline]
+    pub fn sigma_0<T: Word>(x: T) -> T {
+        let [r1, r2, s] = T::SIGMA_0;
+        rot_r(x, r1) ^ rot_r(x, r2) ^ shr(x, s)
     }
 
-    pub fn sigma_1<T:
-        'static +
-        Copy +
-        std::ops::BitOr<Output = T> + 
-        std::ops::Shr<usize, Output = T> +
-        std::ops::Shl<usize, Output = T> +
-        std::ops::BitXor< Output = T>
-        >
-    (x: T) -> Result<T, crate::sha_lib::err_handling::ShaError> {
-        let type_id = std::any::TypeId::of::<T>();
-        match type_id {
-            t if t == std::any::TypeId::of::<u32>() => Ok(rot_r(x,17) ^ rot_r(x,19) ^ shr(x,10)),
-            t if t == std::any::TypeId::of::<u64>() => Ok(rot_r(x,19) ^ rot_r(x,61) ^ shr(x,6)),
-            _ => Err(crate::err_handling::ShaError::CustomError("Invalid integer type".to_string()))
-        }
+    #[inline]
+    pub fn sigma_1<T: Word>(x: T) -> T {
+        let [r1, r2, s] = T::SIGMA_1;
+        rot_r(x, r1) ^ rot_r(x, r2) ^ shr(x, s)
     }
 }
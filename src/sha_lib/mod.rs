@@ -1,7 +1,28 @@
+// The SHA-1/SHA-2 core builds without std; everything that allocates or does I/O needs it
 pub mod types;
 pub mod pre_processing;
 pub mod logic;
 pub mod constants;
 pub mod sha1;
 pub mod sha2;
+#[cfg(feature = "std")]
+pub mod sha3;
 pub mod err_handling;
+pub mod compare;
+pub(crate) mod zeroize;
+#[cfg(feature = "std")]
+pub mod hasher;
+#[cfg(feature = "std")]
+pub mod state;
+#[cfg(feature = "std")]
+pub mod hmac;
+#[cfg(feature = "std")]
+pub mod hkdf;
+#[cfg(feature = "std")]
+pub mod pbkdf2;
+#[cfg(feature = "std")]
+pub mod sp800_185;
+#[cfg(feature = "std")]
+pub mod cavp;
+pub mod trace;
+pub mod const_hash;
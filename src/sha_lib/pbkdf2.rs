@@ -0,0 +1,49 @@
+// PBKDF2 password-based key derivation as defined in RFC 8018, with HMAC as the PRF
+
+use crate::sha_lib::err_handling::ShaError;
+use crate::sha_lib::hmac::Hmac;
+use crate::sha_lib::types::wrappers::ShaAlgorithm;
+use crate::sha_lib::zeroize::Zeroizing;
+
+/// Derives `length` bytes from `password` and `salt` with HMAC-`algorithm`.
+///
+/// The password is turned into HMAC inner and outer states once; every iteration
+/// starts from a copy of them instead of rehashing the key.
+pub fn pbkdf2(algorithm: &ShaAlgorithm, password: &[u8], salt: &[u8], iterations: u32, length: usize) -> Result<Vec<u8>, ShaError> {
+    if iterations == 0 {
+        return Err(ShaError::InvalidIterationCount);
+    }
+    let hash_len = algorithm.output_size();
+    if length as u64 > u32::MAX as u64 * hash_len as u64 {
+        return Err(ShaError::InvalidOutputLength(length));
+    }
+
+    let prf = Hmac::new(algorithm, password)?;
+    let mut derived_key = Vec::with_capacity(length);
+    let mut block_index: u32 = 1;
+    while derived_key.len() < length {
+        let block = f(&prf, salt, iterations, block_index)?;
+        let take = block.len().min(length - derived_key.len());
+        derived_key.extend_from_slice(&block[..take]);
+        block_index += 1;
+    }
+    Ok(derived_key)
+}
+
+// F(P, S, c, i) = U_1 ^ U_2 ^ ... ^ U_c
+fn f(prf: &Hmac, salt: &[u8], iterations: u32, block_index: u32) -> Result<Zeroizing<Vec<u8>>, ShaError> {
+    let mut mac = prf.clone();
+    mac.update(salt)?;
+    mac.update(&block_index.to_be_bytes())?;
+    let mut u = Zeroizing(mac.finalize()?.get_values());
+    let mut block = u.clone();
+    for _ in 1..iterations {
+        let mut mac = prf.clone();
+        mac.update(&u)?;
+        u = Zeroizing(mac.finalize()?.get_values());
+        for (b, x) in block.iter_mut().zip(u.iter()) {
+            *b ^= x;
+        }
+    }
+    Ok(block)
+}
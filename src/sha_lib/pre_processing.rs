@@ -1,28 +1,52 @@
 use crate::types::wrappers::{MessageBlock, PaddingType};
 
 use super::err_handling::ShaError;
+use super::zeroize::{zeroize_value, Zeroize};
+#[cfg(feature = "std")]
+use super::zeroize::Zeroizing;
 
-pub fn padding(msg: &str, pad_config: PaddingType) -> Result<Vec<MessageBlock>, ShaError> {
-    let original_len = msg.len() * 8;
-    let k: usize = {
-        match pad_config {
-            PaddingType::S512 => {
-                ((448 + 512) - ((original_len + 1) % 512)) % 512
-            },
-            PaddingType::S1024 => {
-                ((896 + 1024) - ((original_len + 1) % 1024)) % 1024
-            },
-        }
-    };
+#[cfg(feature = "std")]
+pub fn padding(msg: &[u8], pad_config: PaddingType) -> Result<Vec<MessageBlock>, ShaError> {
+    padding_with_length(msg, (msg.len() * 8) as u128, pad_config)
+}
+
+// Pads a message of `bit_len` bits, stored most significant bit first in the
+// bit_len / 8 bytes of `msg` (rounded up). Unused bits of a partial last byte are ignored.
+#[cfg(feature = "std")]
+pub fn padding_bits(msg: &[u8], bit_len: u64, pad_config: PaddingType) -> Result<Vec<MessageBlock>, ShaError> {
+    check_bit_length(msg, bit_len)?;
+    padding_with_length(msg, bit_len as u128, pad_config)
+}
+
+// `msg` must hold exactly the bit_len / 8 bytes, rounded up, of a `bit_len`-bit message
+fn check_bit_length(msg: &[u8], bit_len: u64) -> Result<(), ShaError> {
+    if msg.len() as u64 != bit_len.div_ceil(8) {
+        Err(ShaError::InvalidMessageLength(bit_len))?
+    }
+    Ok(())
+}
+
+// Pads `msg` as the tail of a message whose total length is `original_len` bits.
+// The length is not checked against `msg`, so callers go through `padding` or `padding_bits`.
+#[cfg(feature = "std")]
+pub(crate) fn padding_with_length(msg: &[u8], original_len: u128, pad_config: PaddingType) -> Result<Vec<MessageBlock>, ShaError> {
+    // Sized for the padded message up front, so that no copy is left behind by a reallocation
+    let block_size = pad_config.block_size();
+    let length_size = block_size / 8;
+    let mut bin_chars: Zeroizing<Vec<u8>> = Zeroizing(Vec::with_capacity((msg.len() + 1 + length_size).div_ceil(block_size) * block_size));
+    bin_chars.extend_from_slice(msg);
 
-    // Append a single '1' bit followed by k '0' bits
-    let mut bin_chars: Vec<u8> = msg.chars().map(|c| c as u8).collect();
-    let ini = if k%8==0 { 1 } else { 0 };
-    bin_chars.push(0b10000000);
-    for _ in ini..(k/8) {
-        bin_chars.push(0b00000000);
+    // Append a single '1' bit right after the last message bit; a partial last byte keeps only its used bits
+    let used_bits = (original_len % 8) as u32;
+    match bin_chars.last_mut() {
+        Some(last) if used_bits != 0 => *last = (*last & !(0xFF >> used_bits)) | (0x80 >> used_bits),
+        _ => bin_chars.push(0b10000000),
     }
 
+    // Followed by '0' bits up to the length field at the end of the last block
+    let zeros_end = (bin_chars.len() + length_size).div_ceil(block_size) * block_size - length_size;
+    bin_chars.resize(zeros_end, 0b00000000);
+
     // Append the length of the original message as a 64 or 128-bit binary number
     match pad_config {
         PaddingType::S512 => {
@@ -30,7 +54,7 @@ pub fn padding(msg: &str, pad_config: PaddingType) -> Result<Vec<MessageBlock>,
             bin_chars.extend_from_slice(&length_bytes);
         },
         PaddingType::S1024 => {
-            let length_bytes: [u8; 16] = (original_len as u128).to_be_bytes();
+            let length_bytes: [u8; 16] = original_len.to_be_bytes();
             bin_chars.extend_from_slice(&length_bytes);
         },
     }
@@ -86,3 +110,164 @@ pub fn padding(msg: &str, pad_config: PaddingType) -> Result<Vec<MessageBlock>,
     Ok(result)
 }
 
+
+// Converts one full block of bytes into its big-endian words
+pub fn to_message_block(bytes: &[u8], pad_config: &PaddingType) -> Result<MessageBlock, ShaError> {
+    match pad_config {
+        PaddingType::S512 => {
+            if bytes.len() != 64 {
+                Err(ShaError::InvalidPadding)?
+            }
+            let mut block: [u32; 16] = [0; 16];
+            for (j, subchunk) in bytes.chunks_exact(4).enumerate() {
+                block[j] = u32::from_be_bytes([subchunk[0], subchunk[1], subchunk[2], subchunk[3]]);
+            }
+            Ok(MessageBlock::Block512(block))
+        },
+        PaddingType::S1024 => {
+            if bytes.len() != 128 {
+                Err(ShaError::InvalidPadding)?
+            }
+            let mut block: [u64; 16] = [0; 16];
+            for (j, subchunk) in bytes.chunks_exact(8).enumerate() {
+                block[j] = u64::from_be_bytes([subchunk[0], subchunk[1], subchunk[2], subchunk[3],
+                                                subchunk[4], subchunk[5], subchunk[6], subchunk[7]]);
+            }
+            Ok(MessageBlock::Block1024(block))
+        },
+    }
+}
+
+// Partial block buffer shared by the streaming hashers
+#[derive(Clone, Debug)]
+pub(crate) struct BlockBuffer {
+    pad_config: PaddingType,
+    bytes: [u8; 128],
+    len: usize,
+    total_len: u128,
+}
+
+impl BlockBuffer {
+    pub fn new(pad_config: PaddingType) -> BlockBuffer {
+        BlockBuffer { pad_config, bytes: [0; 128], len: 0, total_len: 0 }
+    }
+
+    // Buffered bytes and total length in bits, for serializing the hasher state
+    #[cfg(feature = "std")]
+    pub(crate) fn parts(&self) -> (&[u8], u128) {
+        (&self.bytes[..self.len], self.total_len)
+    }
+
+    // Rebuilds a buffer from `parts`, checking that the buffered bytes fit the total length
+    #[cfg(feature = "std")]
+    pub(crate) fn from_parts(pad_config: PaddingType, bytes: &[u8], total_len: u128) -> Result<BlockBuffer, ShaError> {
+        let block_size = pad_config.block_size();
+        if matches!(pad_config, PaddingType::S512) && total_len >= 1 << 64 {
+            Err(ShaError::InvalidState("total length out of range"))?
+        }
+        // A partial last byte may complete the buffered block, which is then padded at finalize
+        let expected = match total_len.div_ceil(8) as usize % block_size {
+            0 if !total_len.is_multiple_of(8) => block_size,
+            len => len,
+        };
+        if bytes.len() != expected {
+            Err(ShaError::InvalidState("buffer doesn't match the total length"))?
+        }
+        let mut buffer = BlockBuffer::new(pad_config);
+        buffer.bytes[..bytes.len()].copy_from_slice(bytes);
+        buffer.len = bytes.len();
+        buffer.total_len = total_len;
+        Ok(buffer)
+    }
+
+    // Buffers `data` and hands the bytes of every completed block to `compress`
+    pub fn update<F>(&mut self, mut data: &[u8], mut compress: F) -> Result<(), ShaError>
+    where
+        F: FnMut(&[u8]) -> Result<(), ShaError>,
+    {
+        if !self.total_len.is_multiple_of(8) {
+            Err(ShaError::InvalidState("can't add data after a partial byte"))?
+        }
+        let block_size = self.pad_config.block_size();
+        self.total_len += data.len() as u128 * 8;
+
+        if self.len > 0 {
+            let take = data.len().min(block_size - self.len);
+            self.bytes[self.len..self.len + take].copy_from_slice(&data[..take]);
+            self.len += take;
+            data = &data[take..];
+            if self.len < block_size {
+                return Ok(());
+            }
+            compress(&self.bytes[..block_size])?;
+            self.len = 0;
+        }
+
+        let mut chunks = data.chunks_exact(block_size);
+        for chunk in chunks.by_ref() {
+            compress(chunk)?;
+        }
+        let rest = chunks.remainder();
+        self.bytes[..rest.len()].copy_from_slice(rest);
+        self.len = rest.len();
+        Ok(())
+    }
+
+    // Buffers the first `bit_len` bits of `data`; a partial last byte ends the message
+    pub fn update_bits<F>(&mut self, data: &[u8], bit_len: u64, mut compress: F) -> Result<(), ShaError>
+    where
+        F: FnMut(&[u8]) -> Result<(), ShaError>,
+    {
+        check_bit_length(data, bit_len)?;
+        let full_bytes = (bit_len / 8) as usize;
+        self.update(&data[..full_bytes], &mut compress)?;
+        if !bit_len.is_multiple_of(8) {
+            self.bytes[self.len] = data[full_bytes];
+            self.len += 1;
+            self.total_len += (bit_len % 8) as u128;
+        }
+        Ok(())
+    }
+
+    // Pads the buffered tail into the final one or two blocks and hands them to `compress`.
+    // Works in place on the stack, so the core doesn't need an allocator.
+    pub fn finalize<F>(&self, mut compress: F) -> Result<(), ShaError>
+    where
+        F: FnMut(&[u8]) -> Result<(), ShaError>,
+    {
+        let mut tail = [0u8; 256];
+        let mut len = self.len;
+        tail[..len].copy_from_slice(&self.bytes[..len]);
+
+        // Append a single '1' bit right after the last message bit; a partial last byte keeps only its used bits
+        let used_bits = (self.total_len % 8) as u32;
+        if used_bits != 0 {
+            tail[len - 1] = (tail[len - 1] & !(0xFF >> used_bits)) | (0x80 >> used_bits);
+        } else {
+            tail[len] = 0b10000000;
+            len += 1;
+        }
+
+        // Followed by '0' bits up to the length field, a 64 or 128-bit number at the end of the last block
+        let block_size = self.pad_config.block_size();
+        let length_size = block_size / 8;
+        let end = (len + length_size).div_ceil(block_size) * block_size;
+        match self.pad_config {
+            PaddingType::S512 => tail[end - 8..end].copy_from_slice(&(self.total_len as u64).to_be_bytes()),
+            PaddingType::S1024 => tail[end - 16..end].copy_from_slice(&self.total_len.to_be_bytes()),
+        }
+
+        let result = tail[..end].chunks_exact(block_size).try_for_each(&mut compress);
+        tail.zeroize();
+        result
+    }
+}
+
+// The buffered bytes are message data
+impl Drop for BlockBuffer {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+        zeroize_value(&mut self.len);
+        zeroize_value(&mut self.total_len);
+    }
+}
@@ -1,82 +1,235 @@
 use core::result::Result;
 use crate::sha_lib::err_handling::ShaError;
-use crate::types::HashResult;
+use crate::sha_lib::compare::verify_digest;
+use crate::types::{HashResult, Sha1Digest};
 use crate::types::wrappers::{PaddingType, MessageBlock, ShaAlgorithm};
+use crate::sha_lib::pre_processing::{to_message_block, BlockBuffer};
+#[cfg(feature = "std")]
 use crate::sha_lib::pre_processing::padding;
+#[cfg(feature = "std")]
+use crate::sha_lib::state;
+use crate::sha_lib::trace::{BlockTrace, Trace};
 use crate::sha_lib::types::extended_nums::u160;
 use crate::sha_lib::logic::operations::rot_l;
 use crate::sha_lib::logic::functions::f;
 use crate::sha_lib::constants::INITIAL_VALUES::SHA1_INITIAL_VALUES;
 use crate::sha_lib::constants::SHA_CONSTANTS::SHA1_K;
+use crate::sha_lib::zeroize::{zeroize_value, Zeroize};
+#[cfg(feature = "std")]
+use crate::sha_lib::zeroize::Zeroizing;
 
 
 pub fn hash_message(msg: &str, algorithm: &ShaAlgorithm) -> Result<HashResult,ShaError> {
-    let pad_config = match algorithm {
-        ShaAlgorithm::SHA1 => PaddingType::S512,
-        _ => return Err(ShaError::InvalidAlgorithm),
-    };
-    let blocks = padding(msg, pad_config)?;
-    let bin_result = match algorithm {
-        ShaAlgorithm::SHA1 => hash(&blocks),
-        _ => return Err(ShaError::InvalidAlgorithm),
-    };
-    bin_result
+    hash_message_bytes(msg.as_bytes(), algorithm)
+}
+
+pub fn hash_message_bytes(msg: &[u8], algorithm: &ShaAlgorithm) -> Result<HashResult,ShaError> {
+    match algorithm {
+        ShaAlgorithm::SHA1 => {
+            let mut hasher = Sha1Hasher::new();
+            hasher.update(msg)?;
+            hasher.finalize()
+        },
+        _ => Err(ShaError::InvalidAlgorithm),
+    }
+}
+
+// Hashes the first `bit_len` bits of `msg`, most significant bit first
+pub fn hash_message_bits(msg: &[u8], bit_len: u64, algorithm: &ShaAlgorithm) -> Result<HashResult,ShaError> {
+    match algorithm {
+        ShaAlgorithm::SHA1 => {
+            let mut hasher = Sha1Hasher::new();
+            hasher.update_bits(msg, bit_len)?;
+            hasher.finalize()
+        },
+        _ => Err(ShaError::InvalidAlgorithm),
+    }
 }
 
 #[allow(dead_code)]
-pub fn hash(message_blocks: &Vec<MessageBlock>) -> Result<HashResult, ShaError> {
-    Ok(sha_1(message_blocks)?)
+pub fn hash(message_blocks: &[MessageBlock]) -> Result<HashResult, ShaError> {
+    sha_1(message_blocks, None)
+}
+
+// Like `hash_message_bytes`, recording every round in `trace`
+#[cfg(feature = "std")]
+pub fn hash_message_traced(msg: &[u8], trace: &mut Trace) -> Result<HashResult, ShaError> {
+    sha_1(&Zeroizing(padding(msg, PaddingType::S512)?), Some(trace))
 }
 
 #[allow(non_snake_case)]
-fn sha_1(message_blocks: &Vec<MessageBlock>) -> Result<HashResult, ShaError> {
+fn sha_1(message_blocks: &[MessageBlock], mut trace: Option<&mut Trace>) -> Result<HashResult, ShaError> {
     let mut H: [u32; 5] = SHA1_INITIAL_VALUES;
+    if let Some(trace) = trace.as_deref_mut() {
+        trace.record_initial_hash(&H);
+    }
     for  block in message_blocks.iter() {
-        //Prepare the schedule
-        let mut schedule = [0; 80];
-        if let MessageBlock::Block512(ref block) = block {
-            for t in 0..16 {
-                    schedule[t] = block[t];
-            }
-            for t in 16..80 {
-                    schedule[t] = rot_l(schedule[t-3] ^ schedule[t-8] ^ schedule[t-14] ^ schedule[t-16], 1);
-            }
-            fn k(t: u8) -> Result<u32, ShaError> {
-                const K: [u32; 4] = SHA1_K;
-                let ret = match t {
-                    0..=19 => K[0],
-                    20..=39 => K[1],
-                    40..=59 => K[2],
-                    60..=79 => K[3],
-                    _ => Err(ShaError::CustomError("Invalid value for t".to_string()))?,
-                };
-                Ok(ret)
-            }
-            let mut a = H[0];
-            let mut b = H[1];
-            let mut c = H[2];
-            let mut d = H[3];
-            let mut e = H[4];
-            for t in 0..80 {
-                let temp: u32 = rot_l(a, 5)
-                    .wrapping_add(f(t, b, c, d)?)
-                    .wrapping_add(e)
-                    .wrapping_add(k(t)?)
-                    .wrapping_add(schedule[t as usize]);
-                e = d;
-                d = c;
-                c = rot_l(b, 30);
-                b = a;
-                a = temp;
+        compress(&mut H, block, trace.as_deref_mut().map(Trace::next_block))?;
+    }
+    Ok(HashResult::U160(u160::new(H[0], H[1], H[2], H[3], H[4])))
+}
+
+#[allow(non_snake_case)]
+fn compress(H: &mut [u32; 5], block: &MessageBlock, mut trace: Option<&mut BlockTrace>) -> Result<(), ShaError> {
+    //Prepare the schedule
+    let mut schedule = [0; 80];
+    if let MessageBlock::Block512(ref block) = block {
+        schedule[..16].copy_from_slice(block);
+        for t in 16..80 {
+                schedule[t] = rot_l(schedule[t-3] ^ schedule[t-8] ^ schedule[t-14] ^ schedule[t-16], 1);
+        }
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.record_schedule(&schedule);
+        }
+        let mut a = H[0];
+        let mut b = H[1];
+        let mut c = H[2];
+        let mut d = H[3];
+        let mut e = H[4];
+        for t in 0..80 {
+            let temp: u32 = rot_l(a, 5)
+                .wrapping_add(f(t, b, c, d)?)
+                .wrapping_add(e)
+                .wrapping_add(SHA1_K[t as usize / 20])
+                .wrapping_add(schedule[t as usize]);
+            e = d;
+            d = c;
+            c = rot_l(b, 30);
+            b = a;
+            a = temp;
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.record_round(&[a, b, c, d, e]);
             }
-            H[0] = H[0].wrapping_add(a);
-            H[1] = H[1].wrapping_add(b);
-            H[2] = H[2].wrapping_add(c);
-            H[3] = H[3].wrapping_add(d);
-            H[4] = H[4].wrapping_add(e);
-        } else {
-            return Err(ShaError::InvalidPadding);
         }
+        H[0] = H[0].wrapping_add(a);
+        H[1] = H[1].wrapping_add(b);
+        H[2] = H[2].wrapping_add(c);
+        H[3] = H[3].wrapping_add(d);
+        H[4] = H[4].wrapping_add(e);
+        if let Some(trace) = trace {
+            trace.record_hash(H);
+        }
+
+        // Both are derived from the message
+        schedule.zeroize();
+        for variable in [&mut a, &mut b, &mut c, &mut d, &mut e] {
+            zeroize_value(variable);
+        }
+        Ok(())
+    } else {
+        Err(ShaError::InvalidPadding)
+    }
+}
+
+// Compresses one block of bytes, wiping its words afterwards
+#[allow(non_snake_case)]
+fn compress_bytes(H: &mut [u32; 5], bytes: &[u8]) -> Result<(), ShaError> {
+    let mut block = to_message_block(bytes, &PaddingType::S512)?;
+    let result = compress(H, &block, None);
+    block.zeroize();
+    result
+}
+
+/// Incremental SHA-1 hasher.
+///
+/// Keeps the chaining value and a partial block, so the message can be fed in
+/// chunks of any size through `update` and is only padded in `finalize`.
+#[allow(non_snake_case)]
+#[derive(Clone, Debug)]
+pub struct Sha1Hasher {
+    H: [u32; 5],
+    buffer: BlockBuffer,
+}
+
+#[allow(non_snake_case)]
+impl Sha1Hasher {
+    pub fn new() -> Sha1Hasher {
+        Sha1Hasher { H: SHA1_INITIAL_VALUES, buffer: BlockBuffer::new(PaddingType::S512) }
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> Result<(), ShaError> {
+        let H = &mut self.H;
+        self.buffer.update(data, |block| compress_bytes(H, block))
+    }
+
+    // Adds the first `bit_len` bits of `data`; nothing can follow a partial byte
+    pub fn update_bits(&mut self, data: &[u8], bit_len: u64) -> Result<(), ShaError> {
+        let H = &mut self.H;
+        self.buffer.update_bits(data, bit_len, |block| compress_bytes(H, block))
+    }
+
+    // Versioned snapshot of the hasher that `import_state` resumes from
+    #[cfg(feature = "std")]
+    pub fn export_state(&self) -> Result<Vec<u8>, ShaError> {
+        let H: Vec<u64> = self.H.iter().map(|&word| word as u64).collect();
+        state::encode(&ShaAlgorithm::SHA1, &H, &self.buffer)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn import_state(blob: &[u8]) -> Result<Sha1Hasher, ShaError> {
+        let (algorithm, H, buffer) = state::decode(blob)?;
+        Sha1Hasher::from_state(algorithm, H, buffer)
+    }
+
+    // Resumes from a state already decoded by `state::decode`
+    #[cfg(feature = "std")]
+    pub(crate) fn from_state(algorithm: ShaAlgorithm, H: Vec<u64>, buffer: BlockBuffer) -> Result<Sha1Hasher, ShaError> {
+        if algorithm != ShaAlgorithm::SHA1 {
+            return Err(ShaError::InvalidState("not a SHA-1 state"));
+        }
+        let mut hasher = Sha1Hasher { H: [0; 5], buffer };
+        for (word, value) in hasher.H.iter_mut().zip(H) {
+            *word = value as u32;
+        }
+        Ok(hasher)
+    }
+
+    pub fn finalize(self) -> Result<HashResult, ShaError> {
+        let H = self.finish()?;
+        Ok(HashResult::U160(u160::new(H[0], H[1], H[2], H[3], H[4])))
+    }
+
+    // Like `finalize`, giving the bytes inline instead of a `HashResult`
+    pub fn finalize_fixed(self) -> Result<Sha1Digest, ShaError> {
+        let mut bytes = [0; 20];
+        for (bytes, word) in bytes.chunks_mut(4).zip(self.finish()?) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        Ok(Sha1Digest::new(bytes))
+    }
+
+    // Checks the digest against `expected` in constant time
+    pub fn verify(self, expected: &[u8]) -> Result<(), ShaError> {
+        verify_digest(self.finalize_fixed()?.as_bytes(), expected)
+    }
+
+    // Writes the 20-byte digest to the start of `out` and returns its length
+    pub fn finalize_into(self, out: &mut [u8]) -> Result<usize, ShaError> {
+        let size = ShaAlgorithm::SHA1.output_size();
+        if out.len() < size {
+            return Err(ShaError::InvalidOutputLength(out.len()));
+        }
+        out[..size].copy_from_slice(self.finalize_fixed()?.as_bytes());
+        Ok(size)
+    }
+
+    // Pads the buffered tail and returns the final chaining value
+    fn finish(mut self) -> Result<[u32; 5], ShaError> {
+        let H = &mut self.H;
+        self.buffer.finalize(|block| compress_bytes(H, block))?;
+        Ok(self.H)
+    }
+}
+
+// The chaining value depends on the message; the buffer wipes itself
+impl Drop for Sha1Hasher {
+    fn drop(&mut self) {
+        self.H.zeroize();
+    }
+}
+
+impl Default for Sha1Hasher {
+    fn default() -> Self {
+        Self::new()
     }
-    Ok(HashResult::U160(u160::new(H[0], H[1], H[2], H[3], H[4])))
 }
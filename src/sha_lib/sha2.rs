@@ -1,300 +1,599 @@
+use core::marker::PhantomData;
 use crate::sha_lib::err_handling::ShaError;
-use crate::types::HashResult;
+use crate::sha_lib::compare::verify_digest;
+use crate::types::{HashResult, Sha224Digest, Sha256Digest, Sha384Digest, Sha512Digest};
 use crate::types::wrappers::{PaddingType, MessageBlock, ShaAlgorithm};
+use crate::sha_lib::pre_processing::BlockBuffer;
+#[cfg(feature = "std")]
 use crate::sha_lib::pre_processing::padding;
+#[cfg(feature = "std")]
+use crate::sha_lib::state;
+use crate::sha_lib::trace::{BlockTrace, Trace};
 use crate::types::extended_nums::{u224, u256, u384, u512};
 use crate::sha_lib::logic::operations::{ch, maj};
 use crate::sha_lib::logic::functions::{sigma_0, sigma_1, csigma_0, csigma_1};
-use crate::sha_lib::constants::INITIAL_VALUES::{InitialValues, Constants, SHA224_INITIAL_VALUES, SHA256_INITIAL_VALUES, SHA384_INITIAL_VALUES, SHA512_INITIAL_VALUES};
-use crate::sha_lib::constants::SHA_CONSTANTS::{SHA224_K, SHA256_K, SHA384_K, SHA512_K};
+use crate::sha_lib::logic::word::Word;
+use crate::sha_lib::constants::INITIAL_VALUES::{SHA224_INITIAL_VALUES, SHA256_INITIAL_VALUES, SHA384_INITIAL_VALUES, SHA512_INITIAL_VALUES};
+use crate::sha_lib::constants::SHA_CONSTANTS::{SHA256_K, SHA512_K};
+use crate::sha_lib::zeroize::{zeroize_value, Zeroize};
+#[cfg(feature = "std")]
+use crate::sha_lib::zeroize::Zeroizing;
 
-pub fn hash_message(msg: &str, algorithm: &ShaAlgorithm) -> Result<HashResult,ShaError> {
-    let pad_config = match algorithm {
-        ShaAlgorithm::SHA1 => return Err(ShaError::InvalidAlgorithm),
-        ShaAlgorithm::SHA224 | ShaAlgorithm::SHA256=> PaddingType::S512,
-        ShaAlgorithm::SHA384 | ShaAlgorithm::SHA512 => PaddingType::S1024,
-        ShaAlgorithm::SHA512T(_) => PaddingType::S1024,
-    };
-    let blocks = padding(msg, pad_config)?;
-    let bin_result = match algorithm {
-        ShaAlgorithm::SHA1 => Err(ShaError::InvalidAlgorithm),
-        ShaAlgorithm::SHA224 => hash(&blocks, ShaAlgorithm::SHA224),
-        ShaAlgorithm::SHA256 => hash(&blocks, ShaAlgorithm::SHA256),
-        ShaAlgorithm::SHA384 => hash(&blocks, ShaAlgorithm::SHA384),
-        ShaAlgorithm::SHA512 => hash(&blocks, ShaAlgorithm::SHA512),
-        ShaAlgorithm::SHA512T(len) => {
-            hash(&blocks, ShaAlgorithm::SHA512T(len.clone()))
+// Evaluates `$body` with `$engine` bound to whichever engine `Sha2Hasher` uses
+macro_rules! with_engine {
+    ($engines:expr, $engine:ident => $body:expr) => {
+        match $engines {
+            Engine::Sha224($engine) => $body,
+            Engine::Sha256($engine) => $body,
+            Engine::Sha384($engine) => $body,
+            Engine::Sha512($engine) => $body,
         }
     };
-    bin_result
 }
 
-#[allow(non_snake_case)]
-pub fn hash(message_blocks: &Vec<MessageBlock>, algorithm: ShaAlgorithm) -> Result<HashResult, ShaError> {
-    let H = obtain_initial_values(&algorithm)?;
-    let K = obtain_constants(&algorithm)?;
-    match algorithm {
-        ShaAlgorithm::SHA224 | ShaAlgorithm::SHA256 => sha_2_small(message_blocks, algorithm, H, K),
-        ShaAlgorithm::SHA384 | ShaAlgorithm::SHA512 => sha_2_large(message_blocks, algorithm, H, K),
-        ShaAlgorithm::SHA512T(t) => {
-            if t < 1 || t > 512 || t % 8 != 0 {
-                Err(ShaError::CustomError("Invalid length for SHA-512/t; must be a multiple of 8 and between 1 and 512".to_string()))?;
-            }
-        
-            // Initialize H with the modified initial values
-            let mut H = SHA512_INITIAL_VALUES;
-            for value in H.iter_mut() {
-                *value ^= 0xa5a5a5a5a5a5a5a5; // XOR with the constant
-            }
-        
-            // Prepare the seed "SHA-512/{len}"
-            let seed = & format!("SHA-512/{}", t);
-        
-            // Hash the seed using SHA-512 with the modified H as the initial state
-            let seed_blocks = padding(seed, PaddingType::S1024);
-            let seed_blocks = match seed_blocks {
-                Ok(blocks) => blocks,
-                Err(e) => Err(e)?,
-            };
-            let seed_hash = sha_2_large(&seed_blocks, ShaAlgorithm::SHA512, InitialValues::Large(H),Constants::Large(SHA512_K));
-
-            // Use the result of the seed hash as the initial values for the actual message hash
-            let H = match seed_hash {
-                Ok(HashResult::U512(result)) => result.get_values(),
-                _ => Err(ShaError::InvalidResult)?,
-            };
-
-            // Perform the actual message hash with the new initial values
-            let result = sha_2_large(message_blocks, algorithm, InitialValues::Large(H), K);
-            match result {
-                Ok(HashResult::U512(u512)) => {
-                    let values = u512.get_values();
-                    let mut result_vec = Vec::new();
-                    for &value in values.iter() {
-                        result_vec.extend_from_slice(&value.to_be_bytes());
-                    }
-                    result_vec.truncate(t as usize / 8);
-                    Ok(HashResult::U512T(result_vec))
-                },
-                _ => Err(ShaError::InvalidResult),
-            }
+pub fn hash_message(msg: &str, algorithm: &ShaAlgorithm) -> Result<HashResult,ShaError> {
+    hash_message_bytes(msg.as_bytes(), algorithm)
+}
+
+pub fn hash_message_bytes(msg: &[u8], algorithm: &ShaAlgorithm) -> Result<HashResult,ShaError> {
+    let mut hasher = Sha2Hasher::new(algorithm)?;
+    hasher.update(msg)?;
+    hasher.finalize()
+}
+
+// Hashes the first `bit_len` bits of `msg`, most significant bit first
+pub fn hash_message_bits(msg: &[u8], bit_len: u64, algorithm: &ShaAlgorithm) -> Result<HashResult,ShaError> {
+    let mut hasher = Sha2Hasher::new(algorithm)?;
+    hasher.update_bits(msg, bit_len)?;
+    hasher.finalize()
+}
+
+// Hashes blocks that are already padded; their word size must match the algorithm
+pub fn hash(message_blocks: &[MessageBlock], algorithm: ShaAlgorithm) -> Result<HashResult, ShaError> {
+    let mut hasher = Sha2Hasher::new(&algorithm)?;
+    with_engine!(&mut hasher.engine, engine => engine.compress_blocks(message_blocks, None))?;
+    hasher.result()
+}
+
+// Like `hash_message_bytes`, recording every round in `trace`
+#[cfg(feature = "std")]
+pub fn hash_message_traced(msg: &[u8], algorithm: &ShaAlgorithm, trace: &mut Trace) -> Result<HashResult, ShaError> {
+    let mut hasher = Sha2Hasher::new(algorithm)?;
+    let blocks = Zeroizing(padding(msg, hasher.padding_type())?);
+    with_engine!(&mut hasher.engine, engine => engine.compress_blocks(&blocks, Some(trace)))?;
+    hasher.result()
+}
+
+/// Word of a SHA-2 algorithm: 32 bits for SHA-224/256 and 64 bits for the others.
+///
+/// Besides the Σ/σ amounts of `Word`, it fixes the round count, the round constants
+/// and the block layout, so they can't be mixed up between the two families.
+pub trait Sha2Word:
+    'static +
+    Word +
+    Default +
+    Into<u64> +
+    core::fmt::Debug +
+    core::ops::BitAnd<Output = Self> +
+    core::ops::Not<Output = Self>
+{
+    const ROUNDS: usize;
+    // K[0..ROUNDS]
+    const K: &'static [Self];
+    const PADDING: PaddingType;
+
+    fn wrapping_add(self, other: Self) -> Self;
+    // Big-endian words of one block of 16 * size_of::<Self>() bytes
+    fn load_block(bytes: &[u8]) -> [Self; 16];
+    fn block_words(block: &MessageBlock) -> Option<&[Self; 16]>;
+    // Writes the leading `out.len()` bytes of the word in big-endian order
+    fn store(self, out: &mut [u8]);
+    // The low bits of `value`, for words restored from a saved state
+    fn truncate_from(value: u64) -> Self;
+}
+
+impl Sha2Word for u32 {
+    const ROUNDS: usize = 64;
+    const K: &'static [u32] = &SHA256_K;
+    const PADDING: PaddingType = PaddingType::S512;
+
+    fn wrapping_add(self, other: u32) -> u32 {
+        u32::wrapping_add(self, other)
+    }
+
+    fn load_block(bytes: &[u8]) -> [u32; 16] {
+        let mut block = [0; 16];
+        for (word, bytes) in block.iter_mut().zip(bytes.chunks_exact(4)) {
+            *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
         }
-        _ => Err(ShaError::InvalidAlgorithm),
+        block
+    }
+
+    fn block_words(block: &MessageBlock) -> Option<&[u32; 16]> {
+        match block {
+            MessageBlock::Block512(words) => Some(words),
+            MessageBlock::Block1024(_) => None,
+        }
+    }
+
+    fn store(self, out: &mut [u8]) {
+        out.copy_from_slice(&self.to_be_bytes()[..out.len()]);
+    }
+
+    fn truncate_from(value: u64) -> u32 {
+        value as u32
     }
 }
 
+impl Sha2Word for u64 {
+    const ROUNDS: usize = 80;
+    const K: &'static [u64] = &SHA512_K;
+    const PADDING: PaddingType = PaddingType::S1024;
 
+    fn wrapping_add(self, other: u64) -> u64 {
+        u64::wrapping_add(self, other)
+    }
 
-fn obtain_constants(algorithm: &ShaAlgorithm) -> Result<Constants,ShaError> {
-    let constants = match algorithm {
-        ShaAlgorithm::SHA224 => Constants::Small(SHA224_K),
-        ShaAlgorithm::SHA256 => Constants::Small(SHA256_K),
-        ShaAlgorithm::SHA384 => Constants::Large(SHA384_K),
-        ShaAlgorithm::SHA512 => Constants::Large(SHA512_K),
-        ShaAlgorithm::SHA512T(_) => Constants::Large(SHA512_K),
-        _ => return Err(ShaError::InvalidAlgorithm),
-    };
-    Ok(constants)
+    fn load_block(bytes: &[u8]) -> [u64; 16] {
+        let mut block = [0; 16];
+        for (word, bytes) in block.iter_mut().zip(bytes.chunks_exact(8)) {
+            *word = u64::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]]);
+        }
+        block
+    }
+
+    fn block_words(block: &MessageBlock) -> Option<&[u64; 16]> {
+        match block {
+            MessageBlock::Block512(_) => None,
+            MessageBlock::Block1024(words) => Some(words),
+        }
+    }
+
+    fn store(self, out: &mut [u8]) {
+        out.copy_from_slice(&self.to_be_bytes()[..out.len()]);
+    }
+
+    fn truncate_from(value: u64) -> u64 {
+        value
+    }
+}
+
+/// Type-level description of a SHA-2 algorithm: its word, initial hash value and result.
+///
+/// SHA-512/t has no descriptor of its own; it is SHA-512 started from the
+/// initial values derived from t.
+pub trait Sha2Descriptor {
+    type Word: Sha2Word;
+    const INITIAL_VALUES: [Self::Word; 8];
+
+    // Digest type of the algorithm, filled without allocating
+    type Output: AsRef<[u8]>;
+
+    #[allow(non_snake_case)]
+    fn result(H: &[Self::Word; 8]) -> HashResult;
+
+    #[allow(non_snake_case)]
+    fn output(H: &[Self::Word; 8]) -> Self::Output;
 }
 
+#[derive(Clone, Copy, Debug)]
+pub struct Sha224;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sha256;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sha384;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sha512;
+
 #[allow(non_snake_case)]
-fn obtain_initial_values(algorithm: &ShaAlgorithm) -> Result<InitialValues,ShaError> {
-    // Initialize the hash values
-    let initial_values = match algorithm {
-        ShaAlgorithm::SHA224 => InitialValues::Small(SHA224_INITIAL_VALUES),
-        ShaAlgorithm::SHA256 => InitialValues::Small(SHA256_INITIAL_VALUES),
-        ShaAlgorithm::SHA384 => InitialValues::Large(SHA384_INITIAL_VALUES),
-        ShaAlgorithm::SHA512 => InitialValues::Large(SHA512_INITIAL_VALUES),
-        ShaAlgorithm::SHA512T(len) => {
-            let mut H = SHA512_INITIAL_VALUES;
-            for value in H.iter_mut() {
-                *value = *value ^ 0xa5a5a5a5a5a5a5a5;
-            }
-            let seed = &mut format!("SHA-512/{}", len);
-            let blocks = padding(seed, PaddingType::S1024);
-            let blocks = match blocks {
-                Ok(blocks) => blocks,
-                Err(e) => Err(e)?,
-            };
-            let compute = sha_2_large(&blocks, ShaAlgorithm::SHA512, InitialValues::Large(H), Constants::Large(SHA512_K));
-            match compute {
-                Ok(HashResult::U512(result)) => {
-                    let values = result.get_values();
-                    InitialValues::Large(values)
-                },
-                Ok(_) => Err(ShaError::InvalidResult)?,
-                Err(e) => Err(e)?,
-            }
-        },
-        _ => Err(ShaError::InvalidAlgorithm)?,
-    };
-    Ok(initial_values)
+impl Sha2Descriptor for Sha224 {
+    type Word = u32;
+    type Output = Sha224Digest;
+    const INITIAL_VALUES: [u32; 8] = SHA224_INITIAL_VALUES;
+
+    fn result(H: &[u32; 8]) -> HashResult {
+        HashResult::U224(u224::new(H[0], H[1], H[2], H[3], H[4], H[5], H[6]))
+    }
+
+    fn output(H: &[u32; 8]) -> Sha224Digest {
+        let mut bytes = [0; 28];
+        store(H, &mut bytes);
+        Sha224Digest::new(bytes)
+    }
 }
 
 #[allow(non_snake_case)]
-fn sha_2_small(message_blocks: &Vec<MessageBlock>, algorithm: ShaAlgorithm, H: InitialValues, K: Constants) -> Result<HashResult,ShaError> {
-        
-    let mut H = match H {
-        InitialValues::Small(values) => values,
-        InitialValues::Large(_) => Err(ShaError::InvalidInitialValues)?,
-    };
-    let K  = match K {
-        Constants::Small(values) => values,
-        Constants::Large(_) => return Err(ShaError::InvalidConstants),
-    };
-        
-        // Iterate over the message blocks until n-block
-        for block in message_blocks.iter() {
-            if let MessageBlock::Block512(ref block) = block {
-
-                //Prepare the schedule
-                let mut schedule: [u32; 64]  = [0; 64];
-                for t in 0..16 {
-                schedule[t] = block[t];
-                }
-                for t in 16..64 {
-                    let sig_1 = sigma_1(schedule[t-2])?;
-                    let sig_0 = sigma_0(schedule[t-15])?;
-                    schedule[t] = {
-                        sig_1
-                            .wrapping_add(schedule[t-7])
-                            .wrapping_add(sig_0)
-                            .wrapping_add(schedule[t-16])
-                    };
-                }
-    
-                //Initialize the working variables
-                let mut a = H[0];
-                let mut b = H[1];
-                let mut c = H[2];
-                let mut d = H[3];
-                let mut e = H[4];
-                let mut f = H[5];
-                let mut g = H[6];
-                let mut h = H[7];
-    
-                //Variables rotation with compresion function
-                for t in 0..64 {
-                    let sig_1 = csigma_1(e)?;
-                    let sig_0 = csigma_0(a)?;
-                    let temp_1: u32 = h
-                        .wrapping_add(sig_1)
-                        .wrapping_add(ch(e, f, g))
-                        .wrapping_add(K[t])
-                        .wrapping_add(schedule[t as usize]);
-                    let temp_2: u32 = sig_0.wrapping_add(maj(a, b, c));
-                    h = g;
-                    g = f;
-                    f = e;
-                    e = d.wrapping_add(temp_1);
-                    d = c;
-                    c = b;
-                    b = a;
-                    a = temp_1.wrapping_add(temp_2);
-                }
-    
-                //Add the compressed chunk to the current hash value
-                H[0] = H[0].wrapping_add(a);
-                H[1] = H[1].wrapping_add(b);
-                H[2] = H[2].wrapping_add(c);
-                H[3] = H[3].wrapping_add(d);
-                H[4] = H[4].wrapping_add(e);
-                H[5] = H[5].wrapping_add(f);
-                H[6] = H[6].wrapping_add(g);
-                H[7] = H[7].wrapping_add(h);
-            } else {
-                Err(ShaError::InvalidPadding)?;
-            }
+impl Sha2Descriptor for Sha256 {
+    type Word = u32;
+    type Output = Sha256Digest;
+    const INITIAL_VALUES: [u32; 8] = SHA256_INITIAL_VALUES;
+
+    fn result(H: &[u32; 8]) -> HashResult {
+        HashResult::U256(u256::new(H[0], H[1], H[2], H[3], H[4], H[5], H[6], H[7]))
+    }
+
+    fn output(H: &[u32; 8]) -> Sha256Digest {
+        let mut bytes = [0; 32];
+        store(H, &mut bytes);
+        Sha256Digest::new(bytes)
+    }
+}
+
+#[allow(non_snake_case)]
+impl Sha2Descriptor for Sha384 {
+    type Word = u64;
+    type Output = Sha384Digest;
+    const INITIAL_VALUES: [u64; 8] = SHA384_INITIAL_VALUES;
+
+    fn result(H: &[u64; 8]) -> HashResult {
+        HashResult::U384(u384::new(H[0], H[1], H[2], H[3], H[4], H[5]))
+    }
+
+    fn output(H: &[u64; 8]) -> Sha384Digest {
+        let mut bytes = [0; 48];
+        store(H, &mut bytes);
+        Sha384Digest::new(bytes)
+    }
+}
+
+#[allow(non_snake_case)]
+impl Sha2Descriptor for Sha512 {
+    type Word = u64;
+    type Output = Sha512Digest;
+    const INITIAL_VALUES: [u64; 8] = SHA512_INITIAL_VALUES;
+
+    fn result(H: &[u64; 8]) -> HashResult {
+        HashResult::U512(u512::new(H[0], H[1], H[2], H[3], H[4], H[5], H[6], H[7]))
+    }
+
+    fn output(H: &[u64; 8]) -> Sha512Digest {
+        let mut bytes = [0; 64];
+        store(H, &mut bytes);
+        Sha512Digest::new(bytes)
+    }
+}
+
+// Writes the leading `out.len()` bytes of a chaining value, big-endian
+#[allow(non_snake_case)]
+fn store<W: Sha2Word>(H: &[W; 8], out: &mut [u8]) {
+    for (bytes, &word) in out.chunks_mut(core::mem::size_of::<W>()).zip(H) {
+        word.store(bytes);
+    }
+}
+
+// The compression function of every SHA-2 algorithm, for either word size
+#[allow(non_snake_case)]
+fn compress<W: Sha2Word>(H: &mut [W; 8], block: &[W; 16], mut trace: Option<&mut BlockTrace>) {
+    //Prepare the schedule
+    let mut schedule = [W::default(); 80];
+    let schedule = &mut schedule[..W::ROUNDS];
+    schedule[..16].copy_from_slice(block);
+    for t in 16..W::ROUNDS {
+        schedule[t] = sigma_1(schedule[t-2])
+            .wrapping_add(schedule[t-7])
+            .wrapping_add(sigma_0(schedule[t-15]))
+            .wrapping_add(schedule[t-16]);
+    }
+
+    if let Some(trace) = trace.as_deref_mut() {
+        trace.record_schedule(schedule);
+    }
+
+    //Initialize the working variables
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *H;
+
+    //Variables rotation with compresion function
+    for (&k, &w) in W::K.iter().zip(schedule.iter()) {
+        let temp_1 = h
+            .wrapping_add(csigma_1(e))
+            .wrapping_add(ch(e, f, g))
+            .wrapping_add(k)
+            .wrapping_add(w);
+        let temp_2 = csigma_0(a).wrapping_add(maj(a, b, c));
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp_1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp_1.wrapping_add(temp_2);
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.record_round(&[a, b, c, d, e, f, g, h]);
         }
-    
-        //Return the hash
-    match algorithm {
-        ShaAlgorithm::SHA224 => Ok(HashResult::U224(u224::new(H[0], H[1], H[2], H[3], H[4], H[5], H[6]))),
-        ShaAlgorithm::SHA256 => Ok(HashResult::U256(u256::new(H[0], H[1], H[2], H[3], H[4], H[5], H[6], H[7]))),
-        _ => Err(ShaError::InvalidAlgorithm),
+    }
+
+    //Add the compressed chunk to the current hash value
+    for (word, working) in H.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(working);
+    }
+    if let Some(trace) = trace {
+        trace.record_hash(H);
+    }
+
+    // Both are derived from the message
+    schedule.zeroize();
+    for variable in [&mut a, &mut b, &mut c, &mut d, &mut e, &mut f, &mut g, &mut h] {
+        zeroize_value(variable);
     }
 }
 
+// Compresses one block of bytes, wiping its words afterwards
 #[allow(non_snake_case)]
-fn sha_2_large(message_blocks: &Vec<MessageBlock>, algorithm: ShaAlgorithm, H: InitialValues, K: Constants) -> Result<HashResult,ShaError> {
+fn compress_bytes<W: Sha2Word>(H: &mut [W; 8], bytes: &[u8]) {
+    let mut block = W::load_block(bytes);
+    compress(H, &block, None);
+    block.zeroize();
+}
 
-    let mut H = match H {
-        InitialValues::Small(_) => Err(ShaError::InvalidInitialValues)?,
-        InitialValues::Large(values) => values,
-    };
-    let K  = match K {
-        Constants::Small(_) => Err(ShaError::InvalidConstants)?,
-        Constants::Large(values) => values,
-    };
-        
-        // Iterate over the message blocks until n-block
+/// Streaming SHA-2 engine for the algorithm described by `A`.
+///
+/// The word size, round count, constants, initial values and block size all come
+/// from `A`, so they always agree. `Sha2Hasher` picks one of these at runtime.
+#[allow(non_snake_case)]
+#[derive(Clone, Debug)]
+pub struct Sha2Engine<A: Sha2Descriptor> {
+    H: [A::Word; 8],
+    buffer: BlockBuffer,
+    algorithm: PhantomData<A>,
+}
+
+#[allow(non_snake_case)]
+impl<A: Sha2Descriptor> Sha2Engine<A> {
+    pub fn new() -> Sha2Engine<A> {
+        Sha2Engine::with_initial_values(A::INITIAL_VALUES)
+    }
+
+    // Starts from another chaining value, as SHA-512/t does
+    fn with_initial_values(H: [A::Word; 8]) -> Sha2Engine<A> {
+        Sha2Engine { H, buffer: BlockBuffer::new(A::Word::PADDING), algorithm: PhantomData }
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> Result<(), ShaError> {
+        let H = &mut self.H;
+        self.buffer.update(data, |block| {
+            compress_bytes(H, block);
+            Ok(())
+        })
+    }
+
+    // Adds the first `bit_len` bits of `data`; nothing can follow a partial byte
+    pub fn update_bits(&mut self, data: &[u8], bit_len: u64) -> Result<(), ShaError> {
+        let H = &mut self.H;
+        self.buffer.update_bits(data, bit_len, |block| {
+            compress_bytes(H, block);
+            Ok(())
+        })
+    }
+
+    pub fn finalize(mut self) -> Result<HashResult, ShaError> {
+        self.pad()?;
+        Ok(self.result())
+    }
+
+    // Like `finalize`, giving the digest type of the algorithm
+    pub fn finalize_fixed(mut self) -> Result<A::Output, ShaError> {
+        self.pad()?;
+        Ok(A::output(&self.H))
+    }
+
+    // Checks the digest against `expected` in constant time
+    pub fn verify(self, expected: &[u8]) -> Result<(), ShaError> {
+        verify_digest(self.finalize_fixed()?.as_ref(), expected)
+    }
+
+    fn result(&self) -> HashResult {
+        A::result(&self.H)
+    }
+
+    // Compresses the padded tail into the chaining value
+    fn pad(&mut self) -> Result<(), ShaError> {
+        let H = &mut self.H;
+        self.buffer.finalize(|block| {
+            compress_bytes(H, block);
+            Ok(())
+        })
+    }
+
+    // Compresses blocks padded elsewhere, which can have the wrong word size
+    fn compress_blocks(&mut self, message_blocks: &[MessageBlock], mut trace: Option<&mut Trace>) -> Result<(), ShaError> {
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.record_initial_hash(&self.H);
+        }
         for block in message_blocks.iter() {
-            if let MessageBlock::Block1024(ref block) = block {
-
-                //Prepare the schedule
-                let mut schedule: [u64; 80]  = [0; 80];
-                for t in 0..16 {
-                schedule[t] = block[t];
-                }
-                for t in 16..80 {
-                    schedule[t] = {
-                        let sig_1 = sigma_1(schedule[t-2])?;
-                        let sig_0 = sigma_0(schedule[t-15])?;
-                        sig_1
-                            .wrapping_add(schedule[t-7])
-                            .wrapping_add(sig_0)
-                            .wrapping_add(schedule[t-16])
-                    };
-                }
-    
-                //Initialize the working variables
-                let mut a = H[0];
-                let mut b = H[1];
-                let mut c = H[2];
-                let mut d = H[3];
-                let mut e = H[4];
-                let mut f = H[5];
-                let mut g = H[6];
-                let mut h = H[7];
-    
-                //Variables rotation with compresion function
-                for t in 0..80 {
-                    let sig_1 = csigma_1(e)?;
-                    let sig_0 = csigma_0(a)?;
-                    let temp_1: u64 = h
-                        .wrapping_add(sig_1)
-                        .wrapping_add(ch(e, f, g))
-                        .wrapping_add(K[t])
-                        .wrapping_add(schedule[t as usize]);
-                    let temp_2: u64 = sig_0.wrapping_add(maj(a, b, c));
-                    h = g;
-                    g = f;
-                    f = e;
-                    e = d.wrapping_add(temp_1);
-                    d = c;
-                    c = b;
-                    b = a;
-                    a = temp_1.wrapping_add(temp_2);
-                }
-    
-                //Add the compressed chunk to the current hash value
-                H[0] = H[0].wrapping_add(a);
-                H[1] = H[1].wrapping_add(b);
-                H[2] = H[2].wrapping_add(c);
-                H[3] = H[3].wrapping_add(d);
-                H[4] = H[4].wrapping_add(e);
-                H[5] = H[5].wrapping_add(f);
-                H[6] = H[6].wrapping_add(g);
-                H[7] = H[7].wrapping_add(h);
-            } else {
-                Err(ShaError::InvalidPadding)?;
+            let words = A::Word::block_words(block).ok_or(ShaError::InvalidPadding)?;
+            compress(&mut self.H, words, trace.as_deref_mut().map(Trace::next_block));
+        }
+        Ok(())
+    }
+
+    // Writes the leading `out.len()` bytes of the chaining value
+    fn store(&self, out: &mut [u8]) {
+        store(&self.H, out);
+    }
+
+    #[cfg(feature = "std")]
+    fn chaining_value(&self) -> Vec<u64> {
+        self.H.iter().map(|&word| word.into()).collect()
+    }
+
+    #[cfg(feature = "std")]
+    fn restore(&mut self, H: &[u64], buffer: BlockBuffer) {
+        for (word, &value) in self.H.iter_mut().zip(H) {
+            *word = A::Word::truncate_from(value);
+        }
+        self.buffer = buffer;
+    }
+}
+
+// The chaining value depends on the message; the buffer wipes itself
+impl<A: Sha2Descriptor> Drop for Sha2Engine<A> {
+    fn drop(&mut self) {
+        self.H.zeroize();
+    }
+}
+
+impl<A: Sha2Descriptor> Default for Sha2Engine<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Engine of each SHA-2 algorithm, SHA-512/t running on the SHA-512 one
+#[derive(Clone, Debug)]
+enum Engine {
+    Sha224(Sha2Engine<Sha224>),
+    Sha256(Sha2Engine<Sha256>),
+    Sha384(Sha2Engine<Sha384>),
+    Sha512(Sha2Engine<Sha512>),
+}
+
+
+#[allow(non_snake_case)]
+fn sha512t_initial_values(t: u16) -> Result<[u64; 8], ShaError> {
+    // FIPS 180-4 §5.3.6: t < 512, and t = 384 is SHA-384 with its own initial values
+    if !(1..512).contains(&t) || !t.is_multiple_of(8) || t == 384 {
+        Err(ShaError::InvalidTruncation(t))?;
+    }
+    let mut H = SHA512_INITIAL_VALUES;
+    for value in H.iter_mut() {
+        *value ^= 0xa5a5a5a5a5a5a5a5;
+    }
+    // SHA-512 of the string "SHA-512/t", with t in decimal
+    let mut seed = *b"SHA-512/000";
+    let digits = if t >= 100 { 3 } else if t >= 10 { 2 } else { 1 };
+    for i in 0..digits {
+        seed[8 + digits - 1 - i] = b'0' + (t / 10u16.pow(i as u32) % 10) as u8;
+    }
+    let mut engine = Sha2Engine::<Sha512>::with_initial_values(H);
+    engine.update(&seed[..8 + digits])?;
+    engine.pad()?;
+    Ok(engine.H)
+}
+
+// Keeps the leftmost t bits of a SHA-512 result
+#[cfg(feature = "std")]
+fn truncate(result: HashResult, t: u16) -> Result<HashResult, ShaError> {
+    match result {
+        HashResult::U512(u512) => {
+            let values = u512.get_values();
+            let mut result_vec = Vec::new();
+            for &value in values.iter() {
+                result_vec.extend_from_slice(&value.to_be_bytes());
             }
+            result_vec.truncate(t as usize / 8);
+            Ok(HashResult::U512T(result_vec))
+        },
+        _ => Err(ShaError::InvalidResult),
+    }
+}
+
+// Without std a SHA-512/t digest has no HashResult form; `Sha2Hasher::finalize_into` writes it instead
+#[cfg(not(feature = "std"))]
+fn truncate(_result: HashResult, _t: u16) -> Result<HashResult, ShaError> {
+    Err(ShaError::InvalidResult)
+}
+
+/// Incremental SHA-2 hasher for any of the SHA-224, SHA-256, SHA-384, SHA-512
+/// and SHA-512/t algorithms.
+///
+/// Keeps the chaining value and a partial block, so the message can be fed in
+/// chunks of any size through `update` and is only padded in `finalize`.
+#[derive(Clone, Debug)]
+pub struct Sha2Hasher {
+    algorithm: ShaAlgorithm,
+    engine: Engine,
+}
+
+#[allow(non_snake_case)]
+impl Sha2Hasher {
+    pub fn new(algorithm: &ShaAlgorithm) -> Result<Sha2Hasher, ShaError> {
+        let engine = match algorithm {
+            ShaAlgorithm::SHA224 => Engine::Sha224(Sha2Engine::new()),
+            ShaAlgorithm::SHA256 => Engine::Sha256(Sha2Engine::new()),
+            ShaAlgorithm::SHA384 => Engine::Sha384(Sha2Engine::new()),
+            ShaAlgorithm::SHA512 => Engine::Sha512(Sha2Engine::new()),
+            ShaAlgorithm::SHA512T(t) => Engine::Sha512(Sha2Engine::with_initial_values(sha512t_initial_values(*t)?)),
+            _ => return Err(ShaError::InvalidAlgorithm),
+        };
+        Ok(Sha2Hasher { algorithm: *algorithm, engine })
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> Result<(), ShaError> {
+        with_engine!(&mut self.engine, engine => engine.update(data))
+    }
+
+    // Adds the first `bit_len` bits of `data`; nothing can follow a partial byte
+    pub fn update_bits(&mut self, data: &[u8], bit_len: u64) -> Result<(), ShaError> {
+        with_engine!(&mut self.engine, engine => engine.update_bits(data, bit_len))
+    }
+
+    // Versioned snapshot of the hasher that `import_state` resumes from
+    #[cfg(feature = "std")]
+    pub fn export_state(&self) -> Result<Vec<u8>, ShaError> {
+        let H = with_engine!(&self.engine, engine => engine.chaining_value());
+        let buffer = with_engine!(&self.engine, engine => &engine.buffer);
+        state::encode(&self.algorithm, &H, buffer)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn import_state(blob: &[u8]) -> Result<Sha2Hasher, ShaError> {
+        let (algorithm, H, buffer) = state::decode(blob)?;
+        Sha2Hasher::from_state(algorithm, H, buffer)
+    }
+
+    // Resumes from a state already decoded by `state::decode`
+    #[cfg(feature = "std")]
+    pub(crate) fn from_state(algorithm: ShaAlgorithm, H: Vec<u64>, buffer: BlockBuffer) -> Result<Sha2Hasher, ShaError> {
+        if algorithm == ShaAlgorithm::SHA1 {
+            return Err(ShaError::InvalidState("not a SHA-2 state"));
+        }
+        let mut hasher = Sha2Hasher::new(&algorithm)?;
+        with_engine!(&mut hasher.engine, engine => engine.restore(&H, buffer));
+        Ok(hasher)
+    }
+
+    pub fn finalize(mut self) -> Result<HashResult, ShaError> {
+        with_engine!(&mut self.engine, engine => engine.pad())?;
+        self.result()
+    }
+
+    // Writes the digest to the start of `out` and returns its length. Unlike
+    // `finalize` it covers SHA-512/t without std.
+    pub fn finalize_into(mut self, out: &mut [u8]) -> Result<usize, ShaError> {
+        let size = self.algorithm.output_size();
+        if out.len() < size {
+            return Err(ShaError::InvalidOutputLength(out.len()));
         }
-    
-        // Return the hash
-        match algorithm {
-            ShaAlgorithm::SHA384 => Ok(HashResult::U384(u384::new(H[0], H[1], H[2], H[3], H[4], H[5]))),
-            ShaAlgorithm::SHA512 | ShaAlgorithm::SHA512T(_) => Ok(HashResult::U512(u512::new(H[0], H[1], H[2], H[3], H[4], H[5], H[6], H[7]))),
-            _ => Err(ShaError::InvalidAlgorithm),
+        with_engine!(&mut self.engine, engine => engine.pad())?;
+        with_engine!(&self.engine, engine => engine.store(&mut out[..size]));
+        Ok(size)
+    }
+
+    // Checks the digest against `expected` in constant time
+    pub fn verify(self, expected: &[u8]) -> Result<(), ShaError> {
+        let mut digest = [0u8; 64];
+        let size = self.finalize_into(&mut digest)?;
+        verify_digest(&digest[..size], expected)
+    }
+
+    #[cfg(feature = "std")]
+    fn padding_type(&self) -> PaddingType {
+        match self.engine {
+            Engine::Sha224(_) | Engine::Sha256(_) => PaddingType::S512,
+            Engine::Sha384(_) | Engine::Sha512(_) => PaddingType::S1024,
         }
-}
\ No newline at end of file
+    }
+
+    // Result of the current chaining value
+    fn result(&self) -> Result<HashResult, ShaError> {
+        let result = with_engine!(&self.engine, engine => engine.result());
+        match self.algorithm {
+            ShaAlgorithm::SHA512T(t) => truncate(result, t),
+            _ => Ok(result),
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use crate::sha_lib::err_handling::ShaError;
+use crate::sha_lib::compare::verify_digest;
+use crate::sha_lib::zeroize::{zeroize_value, Zeroize};
+use crate::sha_lib::types::HashResult;
+use crate::sha_lib::types::wrappers::ShaAlgorithm;
+use crate::sha_lib::types::extended_nums::{u224, u256, u384, u512};
+use crate::sha_lib::constants::SHA3_CONSTANTS::{KECCAK_RC, KECCAK_RHO, SHAKE128_RATE, SHAKE256_RATE};
+
+// Domain separation bits of SHA-3 ("01") followed by the first bit of pad10*1
+const SHA3_SUFFIX: u8 = 0x06;
+// Domain separation bits of SHAKE ("1111") followed by the first bit of pad10*1
+pub(crate) const SHAKE_SUFFIX: u8 = 0x1F;
+
+pub fn hash_message(msg: &str, algorithm: &ShaAlgorithm) -> Result<HashResult,ShaError> {
+    hash_message_bytes(msg.as_bytes(), algorithm)
+}
+
+pub fn hash_message_bytes(msg: &[u8], algorithm: &ShaAlgorithm) -> Result<HashResult,ShaError> {
+    let mut hasher = Sha3Hasher::new(algorithm)?;
+    hasher.update(msg)?;
+    hasher.finalize()
+}
+
+// Rate in bytes of each SHA-3 hash function
+fn rate(algorithm: &ShaAlgorithm) -> Result<usize, ShaError> {
+    match algorithm {
+        ShaAlgorithm::SHA3_224 | ShaAlgorithm::SHA3_256 | ShaAlgorithm::SHA3_384 | ShaAlgorithm::SHA3_512 => Ok(algorithm.block_size()),
+        _ => Err(ShaError::InvalidAlgorithm),
+    }
+}
+
+/// Keccak-f[1600] permutation over the 25 lanes of the state, indexed by x + 5 * y.
+pub fn keccak_f1600(state: &mut [u64; 25]) {
+    let mut c = [0u64; 5];
+    let mut b = [0u64; 25];
+    for rc in KECCAK_RC.iter() {
+        // Theta
+        for x in 0..5 {
+            c[x] = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+        }
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                state[x + 5 * y] ^= d;
+            }
+        }
+
+        // Rho and pi
+        for x in 0..5 {
+            for y in 0..5 {
+                b[y + 5 * ((2 * x + 3 * y) % 5)] = state[x + 5 * y].rotate_left(KECCAK_RHO[x + 5 * y]);
+            }
+        }
+
+        // Chi
+        for y in 0..5 {
+            for x in 0..5 {
+                state[x + 5 * y] = b[x + 5 * y] ^ (!b[(x + 1) % 5 + 5 * y] & b[(x + 2) % 5 + 5 * y]);
+            }
+        }
+
+        // Iota
+        state[0] ^= rc;
+    }
+
+    // Intermediate lanes of the last round
+    c.zeroize();
+    b.zeroize();
+}
+
+/// Keccak sponge over Keccak-f[1600] with a rate given in bytes.
+///
+/// Bytes are absorbed straight into the state, so no block buffer is kept. `pad`
+/// appends the domain separation suffix and switches the sponge to squeezing.
+#[derive(Clone, Debug)]
+pub struct Sponge {
+    state: [u64; 25],
+    rate: usize,
+    position: usize,
+    squeezing: bool,
+}
+
+impl Sponge {
+    pub fn new(rate: usize) -> Result<Sponge, ShaError> {
+        if rate == 0 || rate >= 200 || !rate.is_multiple_of(8) {
+            return Err(ShaError::InvalidRate(rate));
+        }
+        Ok(Sponge::with_rate(rate))
+    }
+
+    pub(crate) fn with_rate(rate: usize) -> Sponge {
+        Sponge { state: [0; 25], rate, position: 0, squeezing: false }
+    }
+
+    pub fn rate(&self) -> usize {
+        self.rate
+    }
+
+    fn xor_byte(&mut self, index: usize, byte: u8) {
+        self.state[index / 8] ^= (byte as u64) << (8 * (index % 8));
+    }
+
+    fn byte(&self, index: usize) -> u8 {
+        (self.state[index / 8] >> (8 * (index % 8))) as u8
+    }
+
+    pub fn absorb(&mut self, data: &[u8]) -> Result<(), ShaError> {
+        if self.squeezing {
+            return Err(ShaError::InvalidState("can't absorb once the sponge is squeezing"));
+        }
+        for &byte in data.iter() {
+            self.xor_byte(self.position, byte);
+            self.position += 1;
+            if self.position == self.rate {
+                keccak_f1600(&mut self.state);
+                self.position = 0;
+            }
+        }
+        Ok(())
+    }
+
+    // Appends the domain bits and pad10*1; `suffix` holds the domain bits followed by the first padding bit
+    pub fn pad(&mut self, suffix: u8) -> Result<(), ShaError> {
+        if self.squeezing {
+            return Err(ShaError::InvalidState("the sponge is already padded"));
+        }
+        self.xor_byte(self.position, suffix);
+        self.xor_byte(self.rate - 1, 0x80);
+        keccak_f1600(&mut self.state);
+        self.position = 0;
+        self.squeezing = true;
+        Ok(())
+    }
+
+    pub fn squeeze(&mut self, out: &mut [u8]) -> Result<(), ShaError> {
+        if !self.squeezing {
+            return Err(ShaError::InvalidState("the sponge must be padded before squeezing"));
+        }
+        for byte in out.iter_mut() {
+            if self.position == self.rate {
+                keccak_f1600(&mut self.state);
+                self.position = 0;
+            }
+            *byte = self.byte(self.position);
+            self.position += 1;
+        }
+        Ok(())
+    }
+}
+
+// The state holds the absorbed input until it is permuted, and is the secret
+// part of a keyed sponge such as KMAC
+impl Drop for Sponge {
+    fn drop(&mut self) {
+        self.state.zeroize();
+        zeroize_value(&mut self.position);
+    }
+}
+
+/// Incremental SHA-3 hasher for SHA3-224, SHA3-256, SHA3-384 and SHA3-512.
+#[derive(Clone, Debug)]
+pub struct Sha3Hasher {
+    algorithm: ShaAlgorithm,
+    sponge: Sponge,
+}
+
+impl Sha3Hasher {
+    pub fn new(algorithm: &ShaAlgorithm) -> Result<Sha3Hasher, ShaError> {
+        Ok(Sha3Hasher { algorithm: *algorithm, sponge: Sponge::new(rate(algorithm)?)? })
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> Result<(), ShaError> {
+        self.sponge.absorb(data)
+    }
+
+    pub fn finalize(mut self) -> Result<HashResult, ShaError> {
+        let mut digest = [0u8; 64];
+        let size = self.squeeze_digest(&mut digest)?;
+        to_hash_result(&digest[..size])
+    }
+
+    // Checks the digest against `expected` in constant time
+    pub fn verify(mut self, expected: &[u8]) -> Result<(), ShaError> {
+        let mut digest = [0u8; 64];
+        let size = self.squeeze_digest(&mut digest)?;
+        verify_digest(&digest[..size], expected)
+    }
+
+    // Pads and squeezes the digest into the start of `out`, returning its length
+    fn squeeze_digest(&mut self, out: &mut [u8; 64]) -> Result<usize, ShaError> {
+        let size = self.algorithm.output_size();
+        self.sponge.pad(SHA3_SUFFIX)?;
+        self.sponge.squeeze(&mut out[..size])?;
+        Ok(size)
+    }
+}
+
+/// SHAKE128 and SHAKE256 extendable-output functions.
+///
+/// The output length carried by `ShaAlgorithm::SHAKE128(bits)` is only used by
+/// `finalize`; `finalize_xof` returns a reader that squeezes any number of bytes.
+#[derive(Clone, Debug)]
+pub struct ShakeHasher {
+    algorithm: ShaAlgorithm,
+    sponge: Sponge,
+}
+
+impl ShakeHasher {
+    pub fn new(algorithm: &ShaAlgorithm) -> Result<ShakeHasher, ShaError> {
+        match algorithm {
+            ShaAlgorithm::SHAKE128(bits) | ShaAlgorithm::SHAKE256(bits) => {
+                if *bits == 0 || !bits.is_multiple_of(8) {
+                    return Err(ShaError::InvalidShakeLength(*bits));
+                }
+            },
+            _ => return Err(ShaError::InvalidAlgorithm),
+        }
+        Ok(ShakeHasher { algorithm: *algorithm, sponge: Sponge::new(algorithm.block_size())? })
+    }
+
+    pub fn shake128() -> ShakeHasher {
+        ShakeHasher { algorithm: ShaAlgorithm::SHAKE128(128), sponge: Sponge::with_rate(SHAKE128_RATE) }
+    }
+
+    pub fn shake256() -> ShakeHasher {
+        ShakeHasher { algorithm: ShaAlgorithm::SHAKE256(256), sponge: Sponge::with_rate(SHAKE256_RATE) }
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> Result<(), ShaError> {
+        self.sponge.absorb(data)
+    }
+
+    pub fn finalize_xof(mut self) -> Result<XofReader, ShaError> {
+        self.sponge.pad(SHAKE_SUFFIX)?;
+        Ok(XofReader::new(self.sponge))
+    }
+
+    // Squeezes the number of bits given by the algorithm
+    pub fn finalize(self) -> Result<HashResult, ShaError> {
+        let mut output = vec![0u8; self.algorithm.output_size()];
+        self.finalize_xof()?.read(&mut output)?;
+        Ok(HashResult::U512T(output))
+    }
+
+    // Checks the output of the length given by the algorithm against `expected`
+    // in constant time
+    pub fn verify(self, expected: &[u8]) -> Result<(), ShaError> {
+        verify_digest(&self.finalize()?.get_values(), expected)
+    }
+}
+
+/// Output of an extendable-output function, read incrementally.
+///
+/// Consecutive reads continue where the previous one stopped, so reading 10 and
+/// then 20 bytes gives the same 30 bytes as a single read.
+#[derive(Clone, Debug)]
+pub struct XofReader {
+    sponge: Sponge,
+}
+
+impl XofReader {
+    // Takes a sponge that has already been padded
+    pub(crate) fn new(sponge: Sponge) -> XofReader {
+        XofReader { sponge }
+    }
+
+    pub fn read(&mut self, out: &mut [u8]) -> Result<(), ShaError> {
+        self.sponge.squeeze(out)
+    }
+}
+
+/// The output never ends: every `read` fills the whole buffer and none returns 0,
+/// so `read_to_end` and `read_to_string` never return. Bound the reader with
+/// `take(n)` or read a fixed length with `read_exact`.
+impl std::io::Read for XofReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.sponge.squeeze(buf).map_err(std::io::Error::other)?;
+        Ok(buf.len())
+    }
+}
+
+/// Computes the SHAKE128 or SHAKE256 output of `msg`, as long as the algorithm asks for.
+pub fn shake(msg: &[u8], algorithm: &ShaAlgorithm) -> Result<HashResult, ShaError> {
+    let mut hasher = ShakeHasher::new(algorithm)?;
+    hasher.update(msg)?;
+    hasher.finalize()
+}
+
+// Packs the digest bytes into the big-endian words of the HashResult of the same size
+fn to_hash_result(digest: &[u8]) -> Result<HashResult, ShaError> {
+    let w32: Vec<u32> = digest.chunks_exact(4).map(|w| u32::from_be_bytes([w[0], w[1], w[2], w[3]])).collect();
+    let w64: Vec<u64> = digest.chunks_exact(8).map(|w| u64::from_be_bytes([w[0], w[1], w[2], w[3], w[4], w[5], w[6], w[7]])).collect();
+    match digest.len() {
+        28 => Ok(HashResult::U224(u224::new(w32[0], w32[1], w32[2], w32[3], w32[4], w32[5], w32[6]))),
+        32 => Ok(HashResult::U256(u256::new(w32[0], w32[1], w32[2], w32[3], w32[4], w32[5], w32[6], w32[7]))),
+        48 => Ok(HashResult::U384(u384::new(w64[0], w64[1], w64[2], w64[3], w64[4], w64[5]))),
+        64 => Ok(HashResult::U512(u512::new(w64[0], w64[1], w64[2], w64[3], w64[4], w64[5], w64[6], w64[7]))),
+        _ => Err(ShaError::InvalidResult),
+    }
+}
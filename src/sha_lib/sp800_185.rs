@@ -0,0 +1,261 @@
+// SHA-3 derived functions of NIST SP 800-185: cSHAKE, KMAC, TupleHash and ParallelHash
+//
+// Output lengths are given in bytes; the encoded length L of the standard is eight times that.
+
+use crate::sha_lib::err_handling::ShaError;
+use crate::sha_lib::compare::verify_mac;
+use crate::sha_lib::zeroize::{Zeroize, Zeroizing};
+use crate::sha_lib::sha3::{ShakeHasher, Sponge, XofReader, SHAKE_SUFFIX};
+use crate::sha_lib::constants::SHA3_CONSTANTS::{SHAKE128_RATE, SHAKE256_RATE};
+
+// Domain separation bits of cSHAKE ("00") followed by the first bit of pad10*1
+const CSHAKE_SUFFIX: u8 = 0x04;
+
+// Shortest KMAC tag accepted by `Kmac::verify`: SP 800-185 §8.4.2 requires L >= 32 bits
+pub const KMAC_MIN_TAG_LEN: usize = 4;
+
+/// Encodes `x` as its big-endian bytes preceded by their count.
+pub fn left_encode(x: u64) -> Vec<u8> {
+    let bytes = x.to_be_bytes();
+    let skip = (x.leading_zeros() as usize / 8).min(7);
+    let mut encoded = vec![(8 - skip) as u8];
+    encoded.extend_from_slice(&bytes[skip..]);
+    encoded
+}
+
+/// Encodes `x` as its big-endian bytes followed by their count.
+pub fn right_encode(x: u64) -> Vec<u8> {
+    let bytes = x.to_be_bytes();
+    let skip = (x.leading_zeros() as usize / 8).min(7);
+    let mut encoded = bytes[skip..].to_vec();
+    encoded.push((8 - skip) as u8);
+    encoded
+}
+
+/// Prefixes `s` with its length in bits, so concatenated strings can be told apart.
+pub fn encode_string(s: &[u8]) -> Vec<u8> {
+    let mut encoded = left_encode(s.len() as u64 * 8);
+    encoded.extend_from_slice(s);
+    encoded
+}
+
+/// Prefixes `x` with left_encode(w) and pads it with zeros to a multiple of `w` bytes.
+pub fn bytepad(x: &[u8], w: usize) -> Vec<u8> {
+    let mut padded = left_encode(w as u64);
+    padded.extend_from_slice(x);
+    padded.resize(padded.len().div_ceil(w) * w, 0);
+    padded
+}
+
+/// cSHAKE128 and cSHAKE256: SHAKE with a function name and a customization string.
+///
+/// With both strings empty cSHAKE is plain SHAKE.
+#[derive(Clone, Debug)]
+pub struct CShake {
+    sponge: Sponge,
+    suffix: u8,
+}
+
+impl CShake {
+    pub fn cshake128(function_name: &[u8], customization: &[u8]) -> Result<CShake, ShaError> {
+        CShake::new(SHAKE128_RATE, function_name, customization)
+    }
+
+    pub fn cshake256(function_name: &[u8], customization: &[u8]) -> Result<CShake, ShaError> {
+        CShake::new(SHAKE256_RATE, function_name, customization)
+    }
+
+    fn new(rate: usize, function_name: &[u8], customization: &[u8]) -> Result<CShake, ShaError> {
+        let mut sponge = Sponge::new(rate)?;
+        if function_name.is_empty() && customization.is_empty() {
+            return Ok(CShake { sponge, suffix: SHAKE_SUFFIX });
+        }
+        let mut prefix = encode_string(function_name);
+        prefix.extend(encode_string(customization));
+        sponge.absorb(&bytepad(&prefix, rate))?;
+        Ok(CShake { sponge, suffix: CSHAKE_SUFFIX })
+    }
+
+    fn rate(&self) -> usize {
+        self.sponge.rate()
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> Result<(), ShaError> {
+        self.sponge.absorb(data)
+    }
+
+    pub fn finalize_xof(mut self) -> Result<XofReader, ShaError> {
+        self.sponge.pad(self.suffix)?;
+        Ok(XofReader::new(self.sponge))
+    }
+
+    pub fn finalize(self, length: usize) -> Result<Vec<u8>, ShaError> {
+        let mut output = vec![0u8; length];
+        self.finalize_xof()?.read(&mut output)?;
+        Ok(output)
+    }
+}
+
+/// KMAC128 and KMAC256, the Keccak message authentication codes.
+///
+/// `finalize` binds the output length into the MAC, so tags of different lengths
+/// are unrelated. `finalize_xof` gives the KMACXOF variant, whose output doesn't
+/// depend on how much of it is read.
+#[derive(Clone, Debug)]
+pub struct Kmac {
+    cshake: CShake,
+}
+
+impl Kmac {
+    pub fn kmac128(key: &[u8], customization: &[u8]) -> Result<Kmac, ShaError> {
+        Kmac::new(CShake::cshake128(b"KMAC", customization)?, key)
+    }
+
+    pub fn kmac256(key: &[u8], customization: &[u8]) -> Result<Kmac, ShaError> {
+        Kmac::new(CShake::cshake256(b"KMAC", customization)?, key)
+    }
+
+    fn new(mut cshake: CShake, key: &[u8]) -> Result<Kmac, ShaError> {
+        let rate = cshake.rate();
+        let encoded_key = Zeroizing(encode_string(key));
+        cshake.update(&Zeroizing(bytepad(&encoded_key, rate)))?;
+        Ok(Kmac { cshake })
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> Result<(), ShaError> {
+        self.cshake.update(data)
+    }
+
+    pub fn finalize_xof(mut self) -> Result<XofReader, ShaError> {
+        self.cshake.update(&right_encode(0))?;
+        self.cshake.finalize_xof()
+    }
+
+    pub fn finalize(mut self, length: usize) -> Result<Vec<u8>, ShaError> {
+        self.cshake.update(&right_encode(length as u64 * 8))?;
+        self.cshake.finalize(length)
+    }
+
+    /// Checks `tag` in constant time against a MAC of `length` bytes.
+    ///
+    /// The length is the verifier's choice, never the tag's: a tag of any other
+    /// length is rejected, and so is a `length` below `KMAC_MIN_TAG_LEN`.
+    pub fn verify(self, tag: &[u8], length: usize) -> Result<(), ShaError> {
+        if length < KMAC_MIN_TAG_LEN {
+            return Err(ShaError::InvalidOutputLength(length));
+        }
+        verify_mac(&self.finalize(length)?, tag)
+    }
+}
+
+/// TupleHash128 and TupleHash256: hashes a sequence of byte strings so that
+/// ("ab", "c") and ("a", "bc") give different outputs.
+#[derive(Clone, Debug)]
+pub struct TupleHash {
+    cshake: CShake,
+}
+
+impl TupleHash {
+    pub fn tuplehash128(customization: &[u8]) -> Result<TupleHash, ShaError> {
+        Ok(TupleHash { cshake: CShake::cshake128(b"TupleHash", customization)? })
+    }
+
+    pub fn tuplehash256(customization: &[u8]) -> Result<TupleHash, ShaError> {
+        Ok(TupleHash { cshake: CShake::cshake256(b"TupleHash", customization)? })
+    }
+
+    // Each call adds one whole element to the tuple
+    pub fn push(&mut self, item: &[u8]) -> Result<(), ShaError> {
+        self.cshake.update(&encode_string(item))
+    }
+
+    pub fn finalize_xof(mut self) -> Result<XofReader, ShaError> {
+        self.cshake.update(&right_encode(0))?;
+        self.cshake.finalize_xof()
+    }
+
+    pub fn finalize(mut self, length: usize) -> Result<Vec<u8>, ShaError> {
+        self.cshake.update(&right_encode(length as u64 * 8))?;
+        self.cshake.finalize(length)
+    }
+}
+
+/// ParallelHash128 and ParallelHash256.
+///
+/// The message is split into blocks of `block_size` bytes that are hashed on their
+/// own with SHAKE, and the chaining values are then hashed together with cSHAKE.
+/// Blocks are streamed into their SHAKE as they arrive, so nothing of the block
+/// size is allocated up front.
+#[derive(Clone, Debug)]
+pub struct ParallelHash {
+    cshake: CShake,
+    block_hasher: ShakeHasher,
+    // SHAKE of the current block and the number of bytes it has absorbed
+    block: ShakeHasher,
+    block_len: usize,
+    block_size: usize,
+    blocks: u64,
+}
+
+impl ParallelHash {
+    pub fn parallelhash128(block_size: usize, customization: &[u8]) -> Result<ParallelHash, ShaError> {
+        ParallelHash::new(CShake::cshake128(b"ParallelHash", customization)?, ShakeHasher::shake128(), block_size)
+    }
+
+    pub fn parallelhash256(block_size: usize, customization: &[u8]) -> Result<ParallelHash, ShaError> {
+        ParallelHash::new(CShake::cshake256(b"ParallelHash", customization)?, ShakeHasher::shake256(), block_size)
+    }
+
+    fn new(mut cshake: CShake, block_hasher: ShakeHasher, block_size: usize) -> Result<ParallelHash, ShaError> {
+        if block_size == 0 {
+            return Err(ShaError::InvalidBlockSize(block_size));
+        }
+        cshake.update(&left_encode(block_size as u64))?;
+        let block = block_hasher.clone();
+        Ok(ParallelHash { cshake, block_hasher, block, block_len: 0, block_size, blocks: 0 })
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) -> Result<(), ShaError> {
+        while !data.is_empty() {
+            let take = (self.block_size - self.block_len).min(data.len());
+            self.block.update(&data[..take])?;
+            self.block_len += take;
+            data = &data[take..];
+            if self.block_len == self.block_size {
+                self.hash_block()?;
+            }
+        }
+        Ok(())
+    }
+
+    // Absorbs the chaining value of the current block, twice the security strength long
+    fn hash_block(&mut self) -> Result<(), ShaError> {
+        let block = core::mem::replace(&mut self.block, self.block_hasher.clone());
+        let mut chaining_value = [0u8; 64];
+        let chaining_value = &mut chaining_value[..200 - self.cshake.rate()];
+        block.finalize_xof()?.read(chaining_value)?;
+        self.cshake.update(chaining_value)?;
+        chaining_value.zeroize();
+        self.block_len = 0;
+        self.blocks += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self, length_bits: u64) -> Result<(), ShaError> {
+        if self.block_len != 0 {
+            self.hash_block()?;
+        }
+        self.cshake.update(&right_encode(self.blocks))?;
+        self.cshake.update(&right_encode(length_bits))
+    }
+
+    pub fn finalize_xof(mut self) -> Result<XofReader, ShaError> {
+        self.finish(0)?;
+        self.cshake.finalize_xof()
+    }
+
+    pub fn finalize(mut self, length: usize) -> Result<Vec<u8>, ShaError> {
+        self.finish(length as u64 * 8)?;
+        self.cshake.finalize(length)
+    }
+}
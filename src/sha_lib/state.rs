@@ -0,0 +1,133 @@
+// Versioned binary encoding of the SHA-1 and SHA-2 streaming state, so a hash
+// can be checkpointed and resumed in another process.
+//
+// Layout, big-endian:
+//   "SHAS" | version (1) | algorithm id (1) | t of SHA-512/t (2) | total length in bits (16)
+//   | buffered bytes (1) | buffered partial block | H words (4 or 8 bytes each)
+//   | SHA-256 of everything before it (32)
+
+use crate::sha_lib::err_handling::ShaError;
+use crate::sha_lib::pre_processing::BlockBuffer;
+use crate::sha_lib::sha2;
+use crate::sha_lib::types::wrappers::{PaddingType, ShaAlgorithm};
+
+const MAGIC: &[u8; 4] = b"SHAS";
+const VERSION: u8 = 1;
+const CHECKSUM_SIZE: usize = 32;
+
+fn algorithm_id(algorithm: &ShaAlgorithm) -> Result<(u8, u16), ShaError> {
+    match algorithm {
+        ShaAlgorithm::SHA1 => Ok((1, 0)),
+        ShaAlgorithm::SHA224 => Ok((2, 0)),
+        ShaAlgorithm::SHA256 => Ok((3, 0)),
+        ShaAlgorithm::SHA384 => Ok((4, 0)),
+        ShaAlgorithm::SHA512 => Ok((5, 0)),
+        ShaAlgorithm::SHA512T(t) => Ok((6, *t)),
+        _ => Err(ShaError::InvalidAlgorithm),
+    }
+}
+
+fn algorithm_from_id(id: u8, t: u16) -> Result<ShaAlgorithm, ShaError> {
+    match (id, t) {
+        (1, 0) => Ok(ShaAlgorithm::SHA1),
+        (2, 0) => Ok(ShaAlgorithm::SHA224),
+        (3, 0) => Ok(ShaAlgorithm::SHA256),
+        (4, 0) => Ok(ShaAlgorithm::SHA384),
+        (5, 0) => Ok(ShaAlgorithm::SHA512),
+        (6, t) => Ok(ShaAlgorithm::SHA512T(t)),
+        _ => Err(ShaError::InvalidState("unknown algorithm id")),
+    }
+}
+
+// SHA-1 and SHA-224/256 chain 32-bit words, the rest 64-bit ones
+fn word_size(algorithm: &ShaAlgorithm) -> usize {
+    match algorithm {
+        ShaAlgorithm::SHA1 | ShaAlgorithm::SHA224 | ShaAlgorithm::SHA256 => 4,
+        _ => 8,
+    }
+}
+
+fn checksum(bytes: &[u8]) -> Result<Vec<u8>, ShaError> {
+    Ok(sha2::hash_message_bytes(bytes, &ShaAlgorithm::SHA256)?.get_values())
+}
+
+/// Encodes the state of a SHA-1 or SHA-2 hasher; `H` holds the chaining words widened to u64.
+#[allow(non_snake_case)]
+pub(crate) fn encode(algorithm: &ShaAlgorithm, H: &[u64], buffer: &BlockBuffer) -> Result<Vec<u8>, ShaError> {
+    let (id, t) = algorithm_id(algorithm)?;
+    let (bytes, total_len) = buffer.parts();
+
+    let mut blob = MAGIC.to_vec();
+    blob.push(VERSION);
+    blob.push(id);
+    blob.extend_from_slice(&t.to_be_bytes());
+    blob.extend_from_slice(&total_len.to_be_bytes());
+    blob.push(bytes.len() as u8);
+    blob.extend_from_slice(bytes);
+    for word in H.iter() {
+        blob.extend_from_slice(&word.to_be_bytes()[8 - word_size(algorithm)..]);
+    }
+    blob.extend(checksum(&blob)?);
+    Ok(blob)
+}
+
+// Reads the fields in order, failing on a blob that ends too early
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ShaError> {
+        if self.bytes.len() < n {
+            return Err(ShaError::InvalidState("truncated state"));
+        }
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn uint(&mut self, n: usize) -> Result<u128, ShaError> {
+        Ok(self.take(n)?.iter().fold(0, |acc, &b| (acc << 8) | b as u128))
+    }
+}
+
+/// Decodes a blob made by `encode` into the algorithm, the chaining words and the buffer.
+#[allow(non_snake_case)]
+pub(crate) fn decode(blob: &[u8]) -> Result<(ShaAlgorithm, Vec<u64>, BlockBuffer), ShaError> {
+    if blob.len() < MAGIC.len() + 1 || &blob[..MAGIC.len()] != MAGIC {
+        return Err(ShaError::InvalidState("not a hasher state"));
+    }
+    if blob[MAGIC.len()] != VERSION {
+        return Err(ShaError::InvalidState("unsupported version"));
+    }
+    if blob.len() < MAGIC.len() + 1 + CHECKSUM_SIZE {
+        return Err(ShaError::InvalidState("truncated state"));
+    }
+    let (body, sum) = blob.split_at(blob.len() - CHECKSUM_SIZE);
+    if checksum(body)? != sum {
+        return Err(ShaError::InvalidState("checksum mismatch"));
+    }
+
+    let mut reader = Reader { bytes: &body[MAGIC.len() + 1..] };
+    let id = reader.uint(1)? as u8;
+    let t = reader.uint(2)? as u16;
+    let algorithm = algorithm_from_id(id, t)?;
+    let total_len = reader.uint(16)?;
+    let buffered = reader.uint(1)? as usize;
+    let bytes = reader.take(buffered)?;
+    let pad_config = match word_size(&algorithm) {
+        4 => PaddingType::S512,
+        _ => PaddingType::S1024,
+    };
+    let buffer = BlockBuffer::from_parts(pad_config, bytes, total_len)?;
+
+    let words = if algorithm == ShaAlgorithm::SHA1 { 5 } else { 8 };
+    let mut H = Vec::with_capacity(words);
+    for _ in 0..words {
+        H.push(reader.uint(word_size(&algorithm))? as u64);
+    }
+    if !reader.bytes.is_empty() {
+        return Err(ShaError::InvalidState("trailing bytes"));
+    }
+    Ok((algorithm, H, buffer))
+}
@@ -0,0 +1,218 @@
+// Round-by-round record of SHA-1 and SHA-2, for following the worked examples
+// in the appendices of FIPS 180
+//
+// Without std the types are empty enums: the engines then always get `None`
+// and the recording calls compile away.
+
+#[cfg(feature = "std")]
+use crate::sha_lib::err_handling::ShaError;
+#[cfg(feature = "std")]
+use crate::sha_lib::sha1;
+#[cfg(feature = "std")]
+use crate::sha_lib::sha2;
+#[cfg(feature = "std")]
+use crate::sha_lib::types::HashResult;
+#[cfg(feature = "std")]
+use crate::sha_lib::types::wrappers::ShaAlgorithm;
+
+/// What happened to one message block. 32-bit words are widened to u64.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockTrace {
+    // Message schedule W[0..79] (W[0..63] for SHA-224/256)
+    pub schedule: Vec<u64>,
+    // Working variables a..e (SHA-1) or a..h (SHA-2) after each round t
+    pub rounds: Vec<Vec<u64>>,
+    // Intermediate hash value H(i) once the block is added
+    pub hash: Vec<u64>,
+}
+
+#[cfg(not(feature = "std"))]
+pub enum BlockTrace {}
+
+/// Trace of a whole hash computation.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace {
+    pub algorithm: ShaAlgorithm,
+    // H(0); for SHA-512/t the derived initial values
+    pub initial_hash: Vec<u64>,
+    pub blocks: Vec<BlockTrace>,
+}
+
+#[cfg(not(feature = "std"))]
+pub enum Trace {}
+
+#[cfg(feature = "std")]
+fn widen<W: Copy + Into<u64>>(words: &[W]) -> Vec<u64> {
+    words.iter().map(|&word| word.into()).collect()
+}
+
+// Recording hooks called by the SHA-1 and SHA-2 engines
+#[cfg(feature = "std")]
+#[allow(non_snake_case)]
+impl BlockTrace {
+    pub(crate) fn record_schedule<W: Copy + Into<u64>>(&mut self, schedule: &[W]) {
+        self.schedule = widen(schedule);
+    }
+
+    pub(crate) fn record_round<W: Copy + Into<u64>>(&mut self, variables: &[W]) {
+        self.rounds.push(widen(variables));
+    }
+
+    pub(crate) fn record_hash<W: Copy + Into<u64>>(&mut self, H: &[W]) {
+        self.hash = widen(H);
+    }
+}
+
+#[cfg(not(feature = "std"))]
+#[allow(non_snake_case)]
+impl BlockTrace {
+    pub(crate) fn record_schedule<W>(&mut self, _schedule: &[W]) {
+        match *self {}
+    }
+
+    pub(crate) fn record_round<W>(&mut self, _variables: &[W]) {
+        match *self {}
+    }
+
+    pub(crate) fn record_hash<W>(&mut self, _H: &[W]) {
+        match *self {}
+    }
+}
+
+#[cfg(not(feature = "std"))]
+#[allow(non_snake_case)]
+impl Trace {
+    pub(crate) fn record_initial_hash<W>(&mut self, _H: &[W]) {
+        match *self {}
+    }
+
+    pub(crate) fn next_block(&mut self) -> &mut BlockTrace {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "std")]
+impl Trace {
+    pub fn new(algorithm: ShaAlgorithm) -> Trace {
+        Trace { algorithm, initial_hash: Vec::new(), blocks: Vec::new() }
+    }
+
+    #[allow(non_snake_case)]
+    pub(crate) fn record_initial_hash<W: Copy + Into<u64>>(&mut self, H: &[W]) {
+        self.initial_hash = widen(H);
+    }
+
+    // Starts the record of the next block
+    pub(crate) fn next_block(&mut self) -> &mut BlockTrace {
+        self.blocks.push(BlockTrace::default());
+        let last = self.blocks.len() - 1;
+        &mut self.blocks[last]
+    }
+
+    // Width of the words in bits
+    pub fn word_bits(&self) -> usize {
+        match self.algorithm {
+            ShaAlgorithm::SHA1 | ShaAlgorithm::SHA224 | ShaAlgorithm::SHA256 => 32,
+            _ => 64,
+        }
+    }
+
+    fn word(&self, value: u64) -> String {
+        format!("{:0width$X}", value, width = self.word_bits() / 4)
+    }
+
+    fn variable_names(&self) -> &'static [&'static str] {
+        match self.algorithm {
+            ShaAlgorithm::SHA1 => &["a", "b", "c", "d", "e"],
+            _ => &["a", "b", "c", "d", "e", "f", "g", "h"],
+        }
+    }
+
+    /// Renders the trace like the examples of FIPS 180 appendices A to C.
+    pub fn to_table(&self) -> String {
+        let mut out = format!("{} trace\n\nInitial hash value:\n", self.algorithm);
+        for (i, value) in self.initial_hash.iter().enumerate() {
+            out += &format!("   H[{}] = {}\n", i, self.word(*value));
+        }
+
+        let width = self.word_bits() / 4;
+        let mut previous = &self.initial_hash;
+        for (n, block) in self.blocks.iter().enumerate() {
+            out += &format!("\nBlock {} of {}\n\nThe words of the message schedule are:\n", n + 1, self.blocks.len());
+            for (t, w) in block.schedule.iter().enumerate() {
+                out += &format!("   W[{}] = {}\n", t, self.word(*w));
+            }
+
+            out += "\n      ";
+            for name in self.variable_names() {
+                out += &format!(" {:>width$}", name, width = width);
+            }
+            out += "\n";
+            for (t, round) in block.rounds.iter().enumerate() {
+                out += &format!("t={:>2}:", t);
+                for value in round.iter() {
+                    out += &format!(" {}", self.word(*value));
+                }
+                out += "\n";
+            }
+
+            out += "\nThe intermediate hash value is:\n";
+            let last_round = block.rounds.last().cloned().unwrap_or_default();
+            for (i, value) in block.hash.iter().enumerate() {
+                let before = previous.get(i).copied().unwrap_or_default();
+                let added = last_round.get(i).copied().unwrap_or_default();
+                out += &format!("   H[{}] = {} + {} = {}\n", i, self.word(before), self.word(added), self.word(*value));
+            }
+            previous = &block.hash;
+        }
+        out
+    }
+
+    /// Exports the trace as a JSON object with the words as hexadecimal strings.
+    pub fn to_json(&self) -> String {
+        let words = |values: &[u64]| -> String {
+            let quoted: Vec<String> = values.iter().map(|v| format!("\"{}\"", self.word(*v).to_lowercase())).collect();
+            format!("[{}]", quoted.join(","))
+        };
+        let blocks: Vec<String> = self.blocks.iter().map(|block| {
+            let rounds: Vec<String> = block.rounds.iter().map(|round| words(round)).collect();
+            format!("{{\"schedule\":{},\"rounds\":[{}],\"hash\":{}}}", words(&block.schedule), rounds.join(","), words(&block.hash))
+        }).collect();
+        format!(
+            "{{\"algorithm\":\"{}\",\"word_bits\":{},\"initial_hash\":{},\"blocks\":[{}]}}",
+            self.algorithm, self.word_bits(), words(&self.initial_hash), blocks.join(","),
+        )
+    }
+
+    /// Exports the trace as CSV, one row per round with W[t] and the working
+    /// variables, then a row with t = "H" holding the intermediate hash value.
+    pub fn to_csv(&self) -> String {
+        let mut out = format!("block,t,W,{}\n", self.variable_names().join(","));
+        let hex = |values: &[u64]| -> String {
+            values.iter().map(|v| self.word(*v).to_lowercase()).collect::<Vec<_>>().join(",")
+        };
+        for (n, block) in self.blocks.iter().enumerate() {
+            for (t, round) in block.rounds.iter().enumerate() {
+                out += &format!("{},{},{},{}\n", n + 1, t, self.word(block.schedule[t]).to_lowercase(), hex(round));
+            }
+            out += &format!("{},H,,{}\n", n + 1, hex(&block.hash));
+        }
+        out
+    }
+}
+
+/// Hashes `msg` with SHA-1 or SHA-2 and records every round.
+#[cfg(feature = "std")]
+pub fn trace_message(msg: &[u8], algorithm: &ShaAlgorithm) -> Result<(HashResult, Trace), ShaError> {
+    let mut trace = Trace::new(*algorithm);
+    let hash = match algorithm {
+        ShaAlgorithm::SHA1 => sha1::hash_message_traced(msg, &mut trace)?,
+        ShaAlgorithm::SHA224 | ShaAlgorithm::SHA256 | ShaAlgorithm::SHA384 | ShaAlgorithm::SHA512 | ShaAlgorithm::SHA512T(_) => {
+            sha2::hash_message_traced(msg, algorithm, &mut trace)?
+        },
+        _ => return Err(ShaError::InvalidAlgorithm),
+    };
+    Ok((hash, trace))
+}
@@ -1,9 +1,22 @@
 pub mod wrappers {
 
+    use crate::sha_lib::constants::SHA3_CONSTANTS::{SHA3_224_RATE, SHA3_256_RATE, SHA3_384_RATE, SHA3_512_RATE, SHAKE128_RATE, SHAKE256_RATE};
+
+    #[derive(Clone, Copy, Debug)]
     pub enum PaddingType {
         S512,
         S1024,
     }
+
+    impl PaddingType {
+        // Length of a message block in bytes
+        pub fn block_size(&self) -> usize {
+            match self {
+                PaddingType::S512 => 64,
+                PaddingType::S1024 => 128,
+            }
+        }
+    }
     
     #[derive(Debug)]
     pub enum MessageBlock {
@@ -12,17 +25,129 @@ pub mod wrappers {
     }
 
 
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     pub enum ShaAlgorithm {
         SHA1,
         SHA224,
         SHA256,
         SHA384,
         SHA512,
-        SHA512T(u16)
+        SHA512T(u16),
+        SHA3_224,
+        SHA3_256,
+        SHA3_384,
+        SHA3_512,
+        SHAKE128(u32), // Output length in bits
+        SHAKE256(u32),
+    }
+
+    impl ShaAlgorithm {
+        // Length of a message block in bytes, the rate for SHA-3
+        pub fn block_size(&self) -> usize {
+            match self {
+                ShaAlgorithm::SHA1 | ShaAlgorithm::SHA224 | ShaAlgorithm::SHA256 => PaddingType::S512.block_size(),
+                ShaAlgorithm::SHA384 | ShaAlgorithm::SHA512 | ShaAlgorithm::SHA512T(_) => PaddingType::S1024.block_size(),
+                ShaAlgorithm::SHA3_224 => SHA3_224_RATE,
+                ShaAlgorithm::SHA3_256 => SHA3_256_RATE,
+                ShaAlgorithm::SHA3_384 => SHA3_384_RATE,
+                ShaAlgorithm::SHA3_512 => SHA3_512_RATE,
+                ShaAlgorithm::SHAKE128(_) => SHAKE128_RATE,
+                ShaAlgorithm::SHAKE256(_) => SHAKE256_RATE,
+            }
+        }
+
+        // Length of the digest in bytes
+        pub fn output_size(&self) -> usize {
+            match self {
+                ShaAlgorithm::SHA1 => 20,
+                ShaAlgorithm::SHA224 => 28,
+                ShaAlgorithm::SHA256 => 32,
+                ShaAlgorithm::SHA384 => 48,
+                ShaAlgorithm::SHA512 => 64,
+                ShaAlgorithm::SHA512T(t) => *t as usize / 8,
+                ShaAlgorithm::SHA3_224 => 28,
+                ShaAlgorithm::SHA3_256 => 32,
+                ShaAlgorithm::SHA3_384 => 48,
+                ShaAlgorithm::SHA3_512 => 64,
+                ShaAlgorithm::SHAKE128(bits) | ShaAlgorithm::SHAKE256(bits) => *bits as usize / 8,
+            }
+        }
+    }
+
+    impl core::fmt::Display for ShaAlgorithm {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            match self {
+                ShaAlgorithm::SHA1 => write!(f, "SHA-1"),
+                ShaAlgorithm::SHA224 => write!(f, "SHA-224"),
+                ShaAlgorithm::SHA256 => write!(f, "SHA-256"),
+                ShaAlgorithm::SHA384 => write!(f, "SHA-384"),
+                ShaAlgorithm::SHA512 => write!(f, "SHA-512"),
+                ShaAlgorithm::SHA512T(t) => write!(f, "SHA-512/{}", t),
+                ShaAlgorithm::SHA3_224 => write!(f, "SHA3-224"),
+                ShaAlgorithm::SHA3_256 => write!(f, "SHA3-256"),
+                ShaAlgorithm::SHA3_384 => write!(f, "SHA3-384"),
+                ShaAlgorithm::SHA3_512 => write!(f, "SHA3-512"),
+                ShaAlgorithm::SHAKE128(bits) => write!(f, "SHAKE128/{}", bits),
+                ShaAlgorithm::SHAKE256(bits) => write!(f, "SHAKE256/{}", bits),
+            }
+        }
+    }
+
+    // Accepts names such as "sha256", "SHA-256", "sha512/224", "sha3-256" or "shake128/512".
+    // A SHAKE without a length gives 128 bits for SHAKE128 and 256 bits for SHAKE256.
+    impl core::str::FromStr for ShaAlgorithm {
+        type Err = crate::sha_lib::err_handling::ShaError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            // Lowercased without '-' and '_', in a fixed buffer so that no allocation is needed
+            let mut buffer = [0u8; 32];
+            let mut len = 0;
+            for byte in s.bytes().filter(|&byte| byte != b'-' && byte != b'_') {
+                if len == buffer.len() {
+                    return Err(crate::sha_lib::err_handling::ShaError::InvalidAlgorithm);
+                }
+                buffer[len] = byte.to_ascii_lowercase();
+                len += 1;
+            }
+            let name = core::str::from_utf8(&buffer[..len]).map_err(|_| crate::sha_lib::err_handling::ShaError::InvalidAlgorithm)?;
+            match name {
+                "sha1" => Ok(ShaAlgorithm::SHA1),
+                "sha224" => Ok(ShaAlgorithm::SHA224),
+                "sha256" => Ok(ShaAlgorithm::SHA256),
+                "sha384" => Ok(ShaAlgorithm::SHA384),
+                "sha512" => Ok(ShaAlgorithm::SHA512),
+                "sha3224" => Ok(ShaAlgorithm::SHA3_224),
+                "sha3256" => Ok(ShaAlgorithm::SHA3_256),
+                "sha3384" => Ok(ShaAlgorithm::SHA3_384),
+                "sha3512" => Ok(ShaAlgorithm::SHA3_512),
+                "shake128" => Ok(ShaAlgorithm::SHAKE128(128)),
+                "shake256" => Ok(ShaAlgorithm::SHAKE256(256)),
+                _ => {
+                    let algorithm = match name.split_once('/') {
+                        Some(("sha512", t)) => t.parse().map(ShaAlgorithm::SHA512T),
+                        Some(("shake128", bits)) => bits.parse().map(ShaAlgorithm::SHAKE128),
+                        Some(("shake256", bits)) => bits.parse().map(ShaAlgorithm::SHAKE256),
+                        _ => return Err(crate::sha_lib::err_handling::ShaError::InvalidAlgorithm),
+                    }.map_err(|_| crate::sha_lib::err_handling::ShaError::InvalidAlgorithm)?;
+                    // A SHAKE output length is whole bytes and not empty
+                    match algorithm {
+                        ShaAlgorithm::SHAKE128(bits) | ShaAlgorithm::SHAKE256(bits) if bits == 0 || !bits.is_multiple_of(8) =>
+                            Err(crate::sha_lib::err_handling::ShaError::InvalidShakeLength(bits)),
+                        _ => Ok(algorithm),
+                    }
+                },
+            }
+        }
     }
     
 }
 
+use crate::sha_lib::compare::constant_time_eq;
+
+/// Result of a hash, with the digest words in the layout of the algorithm.
+///
+/// Two results are equal when their digest bytes are; the comparison takes the
+/// same time wherever they differ.
 #[derive(Debug)]
 #[allow(dead_code)]
 pub enum HashResult {
@@ -31,54 +156,444 @@ pub enum HashResult {
     U256(extended_nums::u256),
     U384(extended_nums::u384),
     U512(extended_nums::u512),
-    U512T(Vec<u8>),
+    #[cfg(feature = "std")]
+    U512T(Vec<u8>), // Variable-length outputs: SHA-512/t digests and SHAKE output
 }
 
 impl HashResult {
+    #[cfg(feature = "std")]
     pub fn get_values(&self) -> Vec<u8> {
+        let mut bytes = vec![0; self.byte_len()];
+        self.write_bytes(&mut bytes);
+        bytes
+    }
+
+    // Length of the digest in bytes
+    fn byte_len(&self) -> usize {
         match self {
-            HashResult::U160(u160) => {
-                let mut result: Vec<u8> = Vec::new();
-                for i in u160.get_values().iter() {
-                    result.extend_from_slice(&i.to_be_bytes());
-                }
-                result
-            },
-            HashResult::U224(u224) => {
-                let mut result: Vec<u8> = Vec::new();
-                for i in u224.get_values().iter() {
-                    result.extend_from_slice(&i.to_be_bytes());
-                }
-                result
-            },
-            HashResult::U256(u256) => {
-                let mut result: Vec<u8> = Vec::new();
-                for i in u256.get_values().iter() {
-                    result.extend_from_slice(&i.to_be_bytes());
-                }
-                result
-            },
-            HashResult::U384(u384) => {
-                let mut result: Vec<u8> = Vec::new();
-                for i in u384.get_values().iter() {
-                    result.extend_from_slice(&i.to_be_bytes());
-                }
-                result
-            },
-            HashResult::U512(u512) => {
-                let mut result: Vec<u8> = Vec::new();
-                for i in u512.get_values().iter() {
-                    result.extend_from_slice(&i.to_be_bytes());
+            HashResult::U160(_) => 20,
+            HashResult::U224(_) => 28,
+            HashResult::U256(_) => 32,
+            HashResult::U384(_) => 48,
+            HashResult::U512(_) => 64,
+            #[cfg(feature = "std")]
+            HashResult::U512T(bytes) => bytes.len(),
+        }
+    }
+
+    // Writes the big-endian digest bytes to `out`, which is `byte_len()` long
+    fn write_bytes(&self, out: &mut [u8]) {
+        fn store<const N: usize>(words: impl IntoIterator<Item = [u8; N]>, out: &mut [u8]) {
+            for (bytes, word) in out.chunks_mut(N).zip(words) {
+                bytes.copy_from_slice(&word);
+            }
+        }
+        match self {
+            HashResult::U160(u160) => store(u160.get_values().map(u32::to_be_bytes), out),
+            HashResult::U224(u224) => store(u224.get_values().map(u32::to_be_bytes), out),
+            HashResult::U256(u256) => store(u256.get_values().map(u32::to_be_bytes), out),
+            HashResult::U384(u384) => store(u384.get_values().map(u64::to_be_bytes), out),
+            HashResult::U512(u512) => store(u512.get_values().map(u64::to_be_bytes), out),
+            #[cfg(feature = "std")]
+            HashResult::U512T(bytes) => out.copy_from_slice(bytes),
+        }
+    }
+
+    // Constant-time comparison of the digest bytes, on the stack for the fixed widths
+    fn ct_eq(&self, other: &HashResult) -> bool {
+        #[cfg(feature = "std")]
+        if let (HashResult::U512T(a), HashResult::U512T(b)) = (self, other) {
+            return constant_time_eq(a, b);
+        }
+        let len = self.byte_len();
+        if len != other.byte_len() || len > 64 {
+            return false;
+        }
+        let (mut a, mut b) = ([0u8; 64], [0u8; 64]);
+        self.write_bytes(&mut a[..len]);
+        other.write_bytes(&mut b[..len]);
+        constant_time_eq(&a[..len], &b[..len])
+    }
+
+    // Packs digest bytes into the fixed-width variant of their length
+    fn from_fixed(bytes: &[u8]) -> HashResult {
+        let w32 = |i: usize| u32::from_be_bytes([bytes[4 * i], bytes[4 * i + 1], bytes[4 * i + 2], bytes[4 * i + 3]]);
+        let w64 = |i: usize| (w32(2 * i) as u64) << 32 | w32(2 * i + 1) as u64;
+        match bytes.len() {
+            20 => HashResult::U160(extended_nums::u160::new(w32(0), w32(1), w32(2), w32(3), w32(4))),
+            28 => HashResult::U224(extended_nums::u224::new(w32(0), w32(1), w32(2), w32(3), w32(4), w32(5), w32(6))),
+            32 => HashResult::U256(extended_nums::u256::new(w32(0), w32(1), w32(2), w32(3), w32(4), w32(5), w32(6), w32(7))),
+            48 => HashResult::U384(extended_nums::u384::new(w64(0), w64(1), w64(2), w64(3), w64(4), w64(5))),
+            64 => HashResult::U512(extended_nums::u512::new(w64(0), w64(1), w64(2), w64(3), w64(4), w64(5), w64(6), w64(7))),
+            // Only called by the digest types below, whose lengths are all listed
+            len => unreachable!("no fixed-width result of {} bytes", len),
+        }
+    }
+
+    // Bytes of the hash wrapped in a Digest, which prints as zero-padded hex
+    #[cfg(feature = "std")]
+    pub fn digest(&self) -> Digest {
+        Digest::new(self.get_values())
+    }
+}
+
+impl PartialEq for HashResult {
+    fn eq(&self, other: &HashResult) -> bool {
+        self.ct_eq(other)
+    }
+}
+
+impl Eq for HashResult {}
+
+/// Digest bytes of a hash, displayed and parsed as hexadecimal.
+///
+/// `Display` and `LowerHex` print lowercase hex, `UpperHex` uppercase, and every
+/// byte always takes two digits. `==` runs in constant time.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct Digest {
+    bytes: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl Digest {
+    pub fn new(bytes: Vec<u8>) -> Digest {
+        Digest { bytes }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq for Digest {
+    fn eq(&self, other: &Digest) -> bool {
+        constant_time_eq(&self.bytes, &other.bytes)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Eq for Digest {}
+
+#[cfg(feature = "std")]
+impl core::hash::Hash for Digest {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state);
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<HashResult> for Digest {
+    fn from(hash: HashResult) -> Digest {
+        hash.digest()
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<&HashResult> for Digest {
+    fn from(hash: &HashResult) -> Digest {
+        hash.digest()
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<[u8]> for Digest {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::LowerHex for Digest {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_hex(&self.bytes, f, false)
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::UpperHex for Digest {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_hex(&self.bytes, f, true)
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for Digest {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::LowerHex::fmt(self, f)
+    }
+}
+
+// Parses a hex string in either case, two digits per byte
+#[cfg(feature = "std")]
+impl core::str::FromStr for Digest {
+    type Err = crate::sha_lib::err_handling::ShaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.len().is_multiple_of(2) {
+            return Err(crate::sha_lib::err_handling::ShaError::InvalidDigest);
+        }
+        let mut bytes = vec![0; s.len() / 2];
+        decode_hex(s, &mut bytes)?;
+        Ok(Digest { bytes })
+    }
+}
+
+// Two hex digits per byte, with a "0x" prefix for the `#` flag
+fn write_hex(bytes: &[u8], f: &mut core::fmt::Formatter<'_>, upper: bool) -> core::fmt::Result {
+    if f.alternate() {
+        write!(f, "0x")?;
+    }
+    for byte in bytes.iter() {
+        if upper {
+            write!(f, "{:02X}", byte)?;
+        } else {
+            write!(f, "{:02x}", byte)?;
+        }
+    }
+    Ok(())
+}
+
+// Decodes exactly `2 * out.len()` hex digits, in either case, into `out`
+fn decode_hex(s: &str, out: &mut [u8]) -> Result<(), crate::sha_lib::err_handling::ShaError> {
+    let digits = s.as_bytes();
+    if digits.len() != 2 * out.len() {
+        return Err(crate::sha_lib::err_handling::ShaError::InvalidDigest);
+    }
+    let digit = |c: u8| (c as char).to_digit(16);
+    for (byte, pair) in out.iter_mut().zip(digits.chunks(2)) {
+        match (digit(pair[0]), digit(pair[1])) {
+            (Some(high), Some(low)) => *byte = ((high << 4) | low) as u8,
+            _ => return Err(crate::sha_lib::err_handling::ShaError::InvalidDigest),
+        }
+    }
+    Ok(())
+}
+
+// Declares a digest type of one algorithm holding its `$len` bytes inline.
+// Equality, ordering and hashing agree with those of the byte slice, so the
+// types also work as map keys looked up by `&[u8]`; `==` runs in constant time.
+macro_rules! fixed_digest {
+    ($(#[$doc:meta])* $name:ident, $len:expr) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, PartialOrd, Ord)]
+        pub struct $name([u8; $len]);
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &$name) -> bool {
+                constant_time_eq(&self.0, &other.0)
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl core::hash::Hash for $name {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.0.hash(state);
+            }
+        }
+
+        impl $name {
+            // Length of the digest in bytes
+            pub const LEN: usize = $len;
+
+            pub const fn new(bytes: [u8; $len]) -> $name {
+                $name(bytes)
+            }
+
+            pub const fn as_bytes(&self) -> &[u8; $len] {
+                &self.0
+            }
+
+            pub const fn into_bytes(self) -> [u8; $len] {
+                self.0
+            }
+        }
+
+        impl From<[u8; $len]> for $name {
+            fn from(bytes: [u8; $len]) -> $name {
+                $name(bytes)
+            }
+        }
+
+        impl From<$name> for [u8; $len] {
+            fn from(digest: $name) -> [u8; $len] {
+                digest.0
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = crate::sha_lib::err_handling::ShaError;
+
+            fn try_from(bytes: &[u8]) -> Result<$name, Self::Error> {
+                let bytes = bytes.try_into()
+                    .map_err(|_| crate::sha_lib::err_handling::ShaError::InvalidDigestLength(bytes.len()))?;
+                Ok($name(bytes))
+            }
+        }
+
+        // Copies the bytes out of a result of the same length, without allocating
+        impl TryFrom<&HashResult> for $name {
+            type Error = crate::sha_lib::err_handling::ShaError;
+
+            fn try_from(hash: &HashResult) -> Result<$name, Self::Error> {
+                if hash.byte_len() != $len {
+                    return Err(crate::sha_lib::err_handling::ShaError::InvalidDigestLength(hash.byte_len()));
                 }
-                result
-            },
-            HashResult::U512T(u512t) => {
-                u512t.clone()
-            },
+                let mut bytes = [0; $len];
+                hash.write_bytes(&mut bytes);
+                Ok($name(bytes))
+            }
+        }
+
+        impl TryFrom<HashResult> for $name {
+            type Error = crate::sha_lib::err_handling::ShaError;
+
+            fn try_from(hash: HashResult) -> Result<$name, Self::Error> {
+                $name::try_from(&hash)
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl From<$name> for Digest {
+            fn from(digest: $name) -> Digest {
+                Digest::new(digest.0.to_vec())
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl core::borrow::Borrow<[u8]> for $name {
+            fn borrow(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{}({})", stringify!($name), self)
+            }
         }
+
+        impl core::fmt::LowerHex for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write_hex(&self.0, f, false)
+            }
+        }
+
+        impl core::fmt::UpperHex for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write_hex(&self.0, f, true)
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write_hex(&self.0, f, false)
+            }
+        }
+
+        impl core::str::FromStr for $name {
+            type Err = crate::sha_lib::err_handling::ShaError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let mut bytes = [0; $len];
+                decode_hex(s, &mut bytes)?;
+                Ok($name(bytes))
+            }
+        }
+    };
+}
+
+fixed_digest!(
+    /// SHA-1 digest.
+    Sha1Digest, 20
+);
+fixed_digest!(
+    /// SHA-224 digest.
+    Sha224Digest, 28
+);
+fixed_digest!(
+    /// SHA-256 digest.
+    Sha256Digest, 32
+);
+fixed_digest!(
+    /// SHA-384 digest.
+    Sha384Digest, 48
+);
+fixed_digest!(
+    /// SHA-512 digest.
+    Sha512Digest, 64
+);
+fixed_digest!(
+    /// SHA-512/224 digest.
+    Sha512_224Digest, 28
+);
+fixed_digest!(
+    /// SHA-512/256 digest.
+    Sha512_256Digest, 32
+);
+fixed_digest!(
+    /// SHA3-224 digest.
+    Sha3_224Digest, 28
+);
+fixed_digest!(
+    /// SHA3-256 digest.
+    Sha3_256Digest, 32
+);
+fixed_digest!(
+    /// SHA3-384 digest.
+    Sha3_384Digest, 48
+);
+fixed_digest!(
+    /// SHA3-512 digest.
+    Sha3_512Digest, 64
+);
+
+// Back to the result each algorithm gives, so the typed digests can be passed
+// to code written against `HashResult`
+macro_rules! fixed_result {
+    ($($name:ident),*) => {
+        $(
+            impl From<$name> for HashResult {
+                fn from(digest: $name) -> HashResult {
+                    HashResult::from_fixed(&digest.0)
+                }
+            }
+        )*
+    };
+}
+
+fixed_result!(Sha1Digest, Sha224Digest, Sha256Digest, Sha384Digest, Sha512Digest,
+    Sha3_224Digest, Sha3_256Digest, Sha3_384Digest, Sha3_512Digest);
+
+// SHA-512/t results are variable length
+#[cfg(feature = "std")]
+impl From<Sha512_224Digest> for HashResult {
+    fn from(digest: Sha512_224Digest) -> HashResult {
+        HashResult::U512T(digest.0.to_vec())
     }
 }
 
+#[cfg(feature = "std")]
+impl From<Sha512_256Digest> for HashResult {
+    fn from(digest: Sha512_256Digest) -> HashResult {
+        HashResult::U512T(digest.0.to_vec())
+    }
+}
 
 
 #[allow(dead_code)]
@@ -139,6 +654,7 @@ pub mod extended_nums {
     }
 
     impl u256 {
+        #[allow(clippy::too_many_arguments)]
         pub fn new(a: u32, b: u32, c: u32, d: u32, e: u32, f: u32, g: u32, h: u32) -> u256 {
             u256 { a, b, c, d, e, f, g, h }
         }
@@ -181,6 +697,7 @@ pub mod extended_nums {
     }
 
     impl u512 {
+        #[allow(clippy::too_many_arguments)]
         pub fn new(a: u64, b: u64, c: u64, d: u64, e: u64, f: u64, g: u64, h: u64) -> u512 {
             u512 { a, b, c, d, e, f, g, h }
         }
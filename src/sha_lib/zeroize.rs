@@ -0,0 +1,82 @@
+// Wiping of secret state: hasher buffers and chaining values, message schedules,
+// working variables and the temporaries of HMAC and the KDFs.
+//
+// The writes are volatile and followed by a compiler fence, so they are kept
+// even when nothing reads the memory afterwards, which is exactly when the
+// optimizer drops a plain `fill(0)`. Copies left behind by moves, or in
+// registers, are out of reach.
+
+use core::sync::atomic::{compiler_fence, Ordering};
+use crate::sha_lib::types::wrappers::MessageBlock;
+
+pub(crate) trait Zeroize {
+    fn zeroize(&mut self);
+}
+
+impl<T: Copy + Default> Zeroize for [T] {
+    fn zeroize(&mut self) {
+        for value in self.iter_mut() {
+            // SAFETY: `value` comes from a `&mut`, so it is valid, aligned and not aliased
+            unsafe { core::ptr::write_volatile(value, T::default()) };
+        }
+        compiler_fence(Ordering::SeqCst);
+    }
+}
+
+impl Zeroize for MessageBlock {
+    fn zeroize(&mut self) {
+        match self {
+            MessageBlock::Block512(words) => words.zeroize(),
+            MessageBlock::Block1024(words) => words.zeroize(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Zeroize for Vec<u8> {
+    fn zeroize(&mut self) {
+        self.as_mut_slice().zeroize();
+    }
+}
+
+#[cfg(feature = "std")]
+impl Zeroize for Vec<MessageBlock> {
+    fn zeroize(&mut self) {
+        for block in self.iter_mut() {
+            block.zeroize();
+        }
+    }
+}
+
+// Wipes a single scalar, such as a working variable
+pub(crate) fn zeroize_value<T: Copy + Default>(value: &mut T) {
+    core::slice::from_mut(value).zeroize();
+}
+
+/// Owned value that is wiped when dropped, for temporaries holding secrets.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub(crate) struct Zeroizing<T: Zeroize>(pub(crate) T);
+
+#[cfg(feature = "std")]
+impl<T: Zeroize> core::ops::Deref for Zeroizing<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Zeroize> core::ops::DerefMut for Zeroizing<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Zeroize> Drop for Zeroizing<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
@@ -0,0 +1,140 @@
+use sha_algorithm::hasher::{self, Hasher};
+use sha_algorithm::pre_processing::padding_bits;
+use sha_algorithm::types::wrappers::PaddingType;
+use sha_algorithm::{sha1, sha2};
+use sha_algorithm::{ShaAlgorithm, ShaError};
+
+fn hash_bits(msg: &[u8], bit_len: u64, algorithm: ShaAlgorithm) -> String {
+    hasher::hash_bits(msg, bit_len, &algorithm).unwrap().digest().to_string()
+}
+
+// Message bytes the bit lengths below are cut from
+fn message() -> Vec<u8> {
+    (0..130u32).map(|i| ((i * 37 + 11) & 0xff) as u8).collect()
+}
+
+fn streamed(msg: &[u8], bit_len: u64, algorithm: ShaAlgorithm, chunk: usize) -> String {
+    let full = (bit_len / 8) as usize;
+    let mut hasher = Hasher::new(&algorithm).unwrap();
+    for part in msg[..full].chunks(chunk) {
+        hasher.update(part).unwrap();
+    }
+    hasher.update_bits(&msg[full..bit_len.div_ceil(8) as usize], bit_len % 8).unwrap();
+    hasher.finalize().unwrap().digest().to_string()
+}
+
+// 5-bit messages of RFC 6234
+#[test]
+fn rfc_6234_examples() {
+    assert_eq!(hash_bits(&[0x98], 5, ShaAlgorithm::SHA1), "29826b003b906e660eff4027ce98af3531ac75ba");
+    assert_eq!(hash_bits(&[0x68], 5, ShaAlgorithm::SHA224), "e3b048552c3c387bcab37f6eb06bb79b96a4aee5ff27f51531a9551c");
+    assert_eq!(hash_bits(&[0x68], 5, ShaAlgorithm::SHA256), "d6d3e02a31a84a8caa9718ed6c2057be09db45e7823eb5079ce7a573a3760f95");
+    assert_eq!(hash_bits(&[0x10], 5, ShaAlgorithm::SHA384),
+        "8d17be79e32b6718e07d8a603eb84ba0478f7fcfd1bb93995f7d1149e09143ac1ffcfc56820e469f3878d957a15a3fe4");
+    assert_eq!(hash_bits(&[0xb0], 5, ShaAlgorithm::SHA512),
+        "d4ee29a9e90985446b913cf1d1376c836f4be2c1cf3cada0720a6bf4857d886a7ecb3c4e4c0fa8c7f95214e41dc1b0d21b22a84cc03bf8ce4845f34dd5bdbad4");
+}
+
+// Lengths on both sides of the block and length field boundaries, one-shot and streamed
+#[test]
+fn lengths_around_block_boundaries() {
+    let msg = message();
+    let vectors: [(ShaAlgorithm, u64, &str); 28] = [
+        (ShaAlgorithm::SHA1, 1, "bb6b3e18f0115b57925241676f5b1ae88747b08a"),
+        (ShaAlgorithm::SHA1, 7, "de3cd3fdc2f795754c489ac0ab2f653bf8008207"),
+        (ShaAlgorithm::SHA1, 447, "9375a3f4a9e8174bd59d30b3e5dfd5e1275718fc"),
+        (ShaAlgorithm::SHA1, 449, "a1ba3c9651204df3ac44624833749231c5adc33c"),
+        (ShaAlgorithm::SHA1, 511, "012d06ac1fdef4cb82b5e02183ca855ee358b5f0"),
+        (ShaAlgorithm::SHA1, 1019, "46e05f2031a85667ec832b12d6df53cc4a1eebe6"),
+        (ShaAlgorithm::SHA224, 1, "d3fe57cb76cdd24e9eb23e7e15684e039c75459beaae100f89712e9d"),
+        (ShaAlgorithm::SHA224, 7, "79ba777658a6ca509eafb3d13ac3680acc8dc8555d991f14016affb7"),
+        (ShaAlgorithm::SHA224, 447, "db41803b48dacb2deb05c66a7417da3f1e893e4ab6d61118f4cd863e"),
+        (ShaAlgorithm::SHA224, 449, "05165ac2feaa71a097e59144a6159fe017ba37ef00ee7f985e080503"),
+        (ShaAlgorithm::SHA224, 511, "dda1a2ee2ea892fdb9530ca09f2e714a15790a23e064819e04cb49cc"),
+        (ShaAlgorithm::SHA224, 1019, "f02a523314c8a1a6a7e442fa3639a7352df4bb7f47c912c00300858e"),
+        (ShaAlgorithm::SHA256, 1, "bd4f9e98beb68c6ead3243b1b4c7fed75fa4feaab1f84795cbd8a98676a2a375"),
+        (ShaAlgorithm::SHA256, 7, "3158835e10eb8c2c0909528d8d0b81dcc762d567534d24c2b990a041fed4288d"),
+        (ShaAlgorithm::SHA256, 447, "debb15d7c8a946e6ab159390ebec6b1a166ebd214c0516fcb13506d66ec7adad"),
+        (ShaAlgorithm::SHA256, 449, "7f0928a907ef6c4f47abe8d6970ecb2efaa5f75cc03147a04e9eb0c3c71ec579"),
+        (ShaAlgorithm::SHA256, 511, "41c9cd8823c1fe7dda3fb8ea9430552b77a7161cd8bb9d8a6d5890af3edeac41"),
+        (ShaAlgorithm::SHA256, 1019, "aab850c953db0a2f46c78ea3258f3154cac57a2b1ee34846a3c38f6d01e896cd"),
+        (ShaAlgorithm::SHA384, 1, "634aa63038a164ae6c7d48b319f2aca0a107908e548519204c6d72dbeac0fdc3c9246674f98e8fd30221ba986e737d61"),
+        (ShaAlgorithm::SHA384, 895, "8ce7363a4b091c607b1a5821a55a73223d234d39a5d1a7b98dee04aa87e2cd27d153109c9ce33342c4e82e0d5bbc9a67"),
+        (ShaAlgorithm::SHA384, 897, "20d2c40f1c6bb4a81d6dbbcbe302742b6fa232e9334adc55a443f5fee329921a87e1d6379626faa763b589d194718fd8"),
+        (ShaAlgorithm::SHA384, 1023, "dc289cbb2d0091c4c013fcf1a6f0c0149184bb7ca6fd1bafb8d3e1ccf53e92b3f218a3a8b39ecb25150e84d6796e053e"),
+        (ShaAlgorithm::SHA384, 1033, "b1c86c19c5336960128fdda2d301f69bc25e0647dbfc59aa77c039c597a2cab924db96bdaf5d980f8aa74cde9ce10010"),
+        (ShaAlgorithm::SHA512, 1, "b4594eb12959fc2e6979b6783554299cc0369f44083a8b0955baefd8830cda22894b0b46c0ed49490e391ad99af856cc1bd96f238c7f2a17cf37aeb7e793395a"),
+        (ShaAlgorithm::SHA512, 895, "f68fe1f169575909d7edecdafbb1400faa27a394fe06d59552e5137318955dad340a209c2f863c7ccc050a158b8b68ab507d66f50edd09f18a4d5b77c08ad307"),
+        (ShaAlgorithm::SHA512, 897, "e71d7088b155847d0cc16da873c895ed1be76f93494512735a2cf92aa9499bb069b3ea5a5ce57acd827b422df6d04d03b853fb6923512341607a97aecb0431aa"),
+        (ShaAlgorithm::SHA512, 1023, "9765594ebfec45973d0cf8413da8551a84c6db3630814d7e2d8f4b0e924984d8449fa4e91165b83bd5d2e969c48f6beb03cfa473f878142c386a7c58385c25e1"),
+        (ShaAlgorithm::SHA512, 1033, "75c14dac68530cbeb36a5586624d8f968ca1121200f444d55c0f7dd6387ae5519383ab692aa1c07f21b4490ef4eda2f8cb9858bfd5699bb26a250f241e8e4ac3"),
+    ];
+    for (algorithm, bit_len, expected) in vectors {
+        let bytes = &msg[..bit_len.div_ceil(8) as usize];
+        assert_eq!(hash_bits(bytes, bit_len, algorithm), expected, "{:?} {} bits", algorithm, bit_len);
+        assert_eq!(streamed(&msg, bit_len, algorithm, 13), expected, "{:?} {} bits streamed", algorithm, bit_len);
+    }
+}
+
+#[test]
+fn whole_bytes_match_byte_api() {
+    let msg = message();
+    for algorithm in [ShaAlgorithm::SHA1, ShaAlgorithm::SHA256, ShaAlgorithm::SHA512, ShaAlgorithm::SHA512T(224)] {
+        for len in [0, 55, 56, 64, 111, 112, 128] {
+            assert_eq!(hash_bits(&msg[..len], len as u64 * 8, algorithm),
+                hasher::hash_bytes(&msg[..len], &algorithm).unwrap().digest().to_string());
+        }
+    }
+    assert_eq!(streamed(&msg, 1021, ShaAlgorithm::SHA512T(256), 7), hash_bits(&msg[..128], 1021, ShaAlgorithm::SHA512T(256)));
+}
+
+#[test]
+fn unused_bits_are_ignored() {
+    assert_eq!(hash_bits(&[0x9f], 5, ShaAlgorithm::SHA1), "29826b003b906e660eff4027ce98af3531ac75ba");
+    let mut hasher = Hasher::new(&ShaAlgorithm::SHA256).unwrap();
+    hasher.update_bits(&[0x6f], 5).unwrap();
+    assert_eq!(hasher.finalize().unwrap().digest().to_string(),
+        "d6d3e02a31a84a8caa9718ed6c2057be09db45e7823eb5079ce7a573a3760f95");
+}
+
+#[test]
+fn invalid_lengths() {
+    assert!(matches!(hasher::hash_bits(&[0x98, 0x00], 5, &ShaAlgorithm::SHA1), Err(ShaError::InvalidMessageLength(5))));
+    assert!(matches!(hasher::hash_bits(&[], 1, &ShaAlgorithm::SHA256), Err(ShaError::InvalidMessageLength(1))));
+
+    // Nothing can follow a partial byte
+    let mut hasher = Hasher::new(&ShaAlgorithm::SHA256).unwrap();
+    hasher.update_bits(&[0x68], 5).unwrap();
+    assert!(hasher.update(b"abc").is_err());
+    assert!(hasher.update_bits(&[0x68], 5).is_err());
+
+    let mut hasher = Hasher::new(&ShaAlgorithm::SHA3_256).unwrap();
+    assert!(matches!(hasher.update_bits(&[0x68], 5), Err(ShaError::InvalidAlgorithm)));
+}
+
+#[test]
+fn padding_bits_matches_hash_bits() {
+    // RFC 6234 5-bit test messages
+    let blocks = padding_bits(&[0x98], 5, PaddingType::S512).unwrap();
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(sha1::hash(&blocks).unwrap().digest().to_string(), "29826b003b906e660eff4027ce98af3531ac75ba");
+    let blocks = padding_bits(&[0x68], 5, PaddingType::S512).unwrap();
+    assert_eq!(sha2::hash(&blocks, ShaAlgorithm::SHA256).unwrap().digest().to_string(),
+        "d6d3e02a31a84a8caa9718ed6c2057be09db45e7823eb5079ce7a573a3760f95");
+
+    let msg = message();
+    for bit_len in [0u64, 447, 448, 1023, 1033] {
+        let bytes = &msg[..bit_len.div_ceil(8) as usize];
+        let blocks = padding_bits(bytes, bit_len, PaddingType::S1024).unwrap();
+        assert_eq!(sha2::hash(&blocks, ShaAlgorithm::SHA512).unwrap().digest().to_string(),
+            hash_bits(bytes, bit_len, ShaAlgorithm::SHA512), "{} bits", bit_len);
+    }
+}
+
+#[test]
+fn padding_bits_checks_length() {
+
+    assert!(matches!(padding_bits(&[], 3, PaddingType::S512), Err(ShaError::InvalidMessageLength(3))));
+    assert!(matches!(padding_bits(&[0; 2], 8, PaddingType::S512), Err(ShaError::InvalidMessageLength(8))));
+    assert!(matches!(padding_bits(&[0], 0, PaddingType::S1024), Err(ShaError::InvalidMessageLength(0))));
+}
@@ -0,0 +1,44 @@
+use sha_algorithm::sha1;
+use sha_algorithm::sha2;
+use sha_algorithm::ShaAlgorithm;
+
+fn hash_bytes(msg: &[u8], algorithm: &ShaAlgorithm) -> String {
+    let hash = match algorithm {
+        ShaAlgorithm::SHA1 => sha1::hash_message_bytes(msg, algorithm),
+        _ => sha2::hash_message_bytes(msg, algorithm),
+    };
+    hash.unwrap().digest().to_string()
+}
+
+// CAVP SHA*ShortMsg.rsp, Len = 8, Msg = d3
+#[test]
+fn cavp_high_bit_byte() {
+    let msg = [0xd3];
+    assert_eq!(hash_bytes(&msg, &ShaAlgorithm::SHA1), "b34db2b72d63f33dbef80fb30e094cc0a91d6322");
+    assert_eq!(hash_bytes(&msg, &ShaAlgorithm::SHA224), "244eeeb91219c6e02a6fd45f19e21fe4d7a4696e32e7e4f292ecf177");
+    assert_eq!(hash_bytes(&msg, &ShaAlgorithm::SHA256), "28969cdfa74a12c82f3bad960b0b000aca2ac329deea5c2328ebc6f2ba9802c1");
+    assert_eq!(hash_bytes(&msg, &ShaAlgorithm::SHA384),
+        "8c883ad50488c1593e0de9dac1f0f7ddfccde919e361840be440bb821fe301eee03b0f3f36735897490f985a1baf6940");
+    assert_eq!(hash_bytes(&msg, &ShaAlgorithm::SHA512),
+        "fff202f312fd00eda106601b2e7abeafe3edf1395753c76a088ddd7de34092dbff7bf054053f837abe555bc7bff7f680cd1ed22bf83b476b6c1812b61fa602a6");
+    assert_eq!(hash_bytes(&msg, &ShaAlgorithm::SHA512T(224)), "c9e5d3a641b0d3c89451a5b41703420e595666ce0f2195de418f2649");
+    assert_eq!(hash_bytes(&msg, &ShaAlgorithm::SHA512T(256)), "5e1275ca54860d187b9fdf8a0fd7f48843bb95d066e99f62314fcfef4a4560ff");
+}
+
+#[test]
+fn every_byte_value() {
+    let msg: Vec<u8> = (0..=255).collect();
+    assert_eq!(hash_bytes(&msg, &ShaAlgorithm::SHA1), "4916d6bdb7f78e6803698cab32d1586ea457dfc8");
+    assert_eq!(hash_bytes(&msg, &ShaAlgorithm::SHA256), "40aff2e9d2d8922e47afd4648e6967497158785fbd1da870e7110266bf944880");
+    assert_eq!(hash_bytes(&msg, &ShaAlgorithm::SHA512),
+        "1e7b80bc8edc552c8feeb2780e111477e5bc70465fac1a77b29b35980c3f0ce4a036a6c9462036824bd56801e62af7e9feba5c22ed8a5af877bf7de117dcac6d");
+}
+
+#[test]
+fn str_entry_points_hash_utf8_bytes() {
+    assert_eq!(sha1::hash_message("é", &ShaAlgorithm::SHA1).unwrap().get_values(),
+        sha1::hash_message_bytes(&[0xc3, 0xa9], &ShaAlgorithm::SHA1).unwrap().get_values());
+    assert_eq!(sha2::hash_message("é", &ShaAlgorithm::SHA256).unwrap().get_values(),
+        sha2::hash_message_bytes(&[0xc3, 0xa9], &ShaAlgorithm::SHA256).unwrap().get_values());
+    assert_eq!(hash_bytes("é".as_bytes(), &ShaAlgorithm::SHA256), "4a99557e4033c3539de2eb65472017cad5f9557f7a0625a09f1c3f6e2ba69c4c");
+}
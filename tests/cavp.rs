@@ -0,0 +1,173 @@
+use sha_algorithm::cavp::{self, TestVector};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use sha_algorithm::{Digest, ShaAlgorithm};
+
+// Samples in the CAVP response file format, with the headers, sections and
+// CRLF line endings of the published files. They are not NIST data: the expected
+// values come from Python, as their headers say. `nist_files_pass` runs the real ones.
+const DATA: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/cavp");
+
+fn run_file(name: &str) -> Vec<cavp::TestResult> {
+    cavp::run_file(format!("{}/{}", DATA, name), None).unwrap()
+}
+
+#[test]
+fn sample_files_pass() {
+    for (name, count) in [
+        ("SHA1ShortMsg.rsp", 7),
+        ("SHA1ShortMsg_bits.rsp", 8),
+        ("SHA256ShortMsg.rsp", 5),
+        ("SHA384LongMsg.rsp", 2),
+        ("SHA512_224ShortMsg.rsp", 3),
+        ("SHA512_256ShortMsg.rsp", 3),
+        ("HMAC.rsp", 15),
+        ("SHA1Monte.rsp", 100),
+        ("SHA224Monte.rsp", 100),
+        ("SHA256Monte.rsp", 100),
+        ("SHA384Monte.rsp", 100),
+        ("SHA512Monte.rsp", 100),
+        ("SHA512_224Monte.rsp", 100),
+        ("SHA512_256Monte.rsp", 100),
+    ] {
+        let results = run_file(name);
+        assert_eq!(results.len(), count, "{}", name);
+        assert!(results.iter().all(|result| result.passed), "{}", name);
+    }
+}
+
+#[test]
+fn algorithm_detection() {
+    let results = run_file("SHA512_256ShortMsg.rsp");
+    assert!(matches!(results[0].entry.vector, TestVector::Hash { algorithm: ShaAlgorithm::SHA512T(256), .. }));
+
+    // Without a header the file name and then L decide
+    let text = "[L = 32]\n\nLen = 8\nMsg = 36\nMD = 0000\n";
+    let entries = cavp::parse(text, "SHA512_256ShortMsg.rsp", None).unwrap();
+    assert!(matches!(entries[0].vector, TestVector::Hash { algorithm: ShaAlgorithm::SHA512T(256), .. }));
+    let entries = cavp::parse(text, "vectors.rsp", None).unwrap();
+    assert!(matches!(entries[0].vector, TestVector::Hash { algorithm: ShaAlgorithm::SHA256, .. }));
+    assert_eq!(entries[0].line, 3);
+    assert_eq!(entries[0].vector.to_string(), "SHA-256 Len = 8");
+
+    // An explicit algorithm must agree with L
+    assert!(cavp::parse(text, "vectors.rsp", Some(ShaAlgorithm::SHA512T(256))).is_ok());
+    assert!(cavp::parse(text, "vectors.rsp", Some(ShaAlgorithm::SHA1)).is_err());
+}
+
+#[test]
+fn mismatches_fail() {
+    let text = "[L = 20]\n\nLen = 8\nMsg = 36\nMD = c1dfd96eea8cc2b62785275bca38ac261256e278\n\n\
+                Len = 8\nMsg = 37\nMD = c1dfd96eea8cc2b62785275bca38ac261256e278\n";
+    let results = cavp::run(&cavp::parse(text, "SHA1ShortMsg.rsp", None).unwrap());
+    assert_eq!(results.iter().map(|result| result.passed).collect::<Vec<_>>(), [true, false]);
+    assert_eq!(results[1].entry.line, 7);
+
+    let text = "[L=20]\n\nCount = 0\nKlen = 1\nTlen = 4\nKey = 00\nMsg = 00\nMac = 00000000\n";
+    let results = cavp::run(&cavp::parse(text, "HMAC.rsp", None).unwrap());
+    assert!(!results[0].passed);
+    assert_eq!(results[0].entry.vector.to_string(), "HMAC-SHA-1 Count = 0");
+}
+
+#[test]
+fn malformed_files() {
+    // Incomplete vector
+    assert!(cavp::parse("[L = 20]\nLen = 8\nMsg = 36\n", "SHA1ShortMsg.rsp", None).is_err());
+    // Msg shorter than Len
+    assert!(cavp::parse("[L = 20]\nLen = 16\nMsg = 36\nMD = 00\n", "SHA1ShortMsg.rsp", None).is_err());
+    // Not hexadecimal
+    assert!(cavp::parse("[L = 20]\nLen = 8\nMsg = zz\nMD = 00\n", "SHA1ShortMsg.rsp", None).is_err());
+    // Unknown algorithm
+    assert!(cavp::parse("Len = 8\nMsg = 36\nMD = 00\n", "vectors.rsp", None).is_err());
+    // SHA-3 files aren't FIPS 180 vectors
+    assert!(cavp::parse("#  \"SHA3-256 ShortMsg\" information\n[L = 256]\nLen = 8\nMsg = 36\nMD = 00\n", "x.rsp", None).is_err());
+    // HMAC vectors need their section and matching lengths
+    assert!(cavp::parse("Count = 0\nKlen = 1\nTlen = 1\nKey = 00\nMsg = 00\nMac = 00\n", "HMAC.rsp", None).is_err());
+    assert!(cavp::parse("[L=20]\nCount = 0\nKlen = 2\nTlen = 1\nKey = 00\nMsg = 00\nMac = 00\n", "HMAC.rsp", None).is_err());
+}
+
+#[test]
+fn monte_carlo_chain() {
+    let seed = Digest::from_str("f4af35548a1fe1b7a3114f681481f0db79a3ff9924aecf68d081eb56046e5f92").unwrap();
+    let checkpoints = cavp::monte_carlo(&ShaAlgorithm::SHA256, seed.as_bytes(), cavp::MONTE_CHECKPOINTS).unwrap();
+    assert_eq!(checkpoints.len(), 100);
+    assert_eq!(Digest::new(checkpoints[99].clone()).to_string(),
+        "16476de203e5a92801059aa613e36c30bd765c65c59502abbf8ea2ec874bc6e5");
+
+    // The seed must be one digest long
+    assert!(cavp::monte_carlo_checkpoint(&ShaAlgorithm::SHA256, &[0; 20]).is_err());
+    assert!(cavp::monte_carlo_checkpoint(&ShaAlgorithm::SHA3_256, &[0; 32]).is_err());
+}
+
+#[test]
+fn monte_carlo_seeds() {
+    let text = std::fs::read_to_string(format!("{}/SHA224Monte.rsp", DATA)).unwrap();
+    let entries = cavp::parse(&text, "SHA224Monte.rsp", None).unwrap();
+    let TestVector::Monte { md: first_md, .. } = &entries[0].vector else {
+        panic!("expected a Monte Carlo vector");
+    };
+    // Each checkpoint starts from the expected digest of the previous one
+    assert!(matches!(&entries[1].vector, TestVector::Monte { seed, count: 1, .. } if seed == first_md));
+    assert_eq!(entries[1].vector.to_string(), "SHA-224 Monte COUNT = 1");
+
+    // So a wrong checkpoint also fails the one after it
+    let md = Digest::new(first_md.clone()).to_string();
+    let text = text.replacen(&md, &"0".repeat(md.len()), 1);
+    let results = cavp::run(&cavp::parse(&text, "SHA224Monte.rsp", None).unwrap()[..3]);
+    assert_eq!(results.iter().map(|result| result.passed).collect::<Vec<_>>(), [false, false, true]);
+
+    // Seeds and checkpoints must be digests of the algorithm
+    assert!(cavp::parse("[L = 20]\nSeed = 00\nCOUNT = 0\nMD = 00\n", "SHA1Monte.rsp", None).is_err());
+    assert!(cavp::parse("[L = 20]\nSeed = zz\n", "SHA1Monte.rsp", None).is_err());
+}
+
+// Response files below `dir`, in subdirectories too, as the NIST archives unpack
+fn rsp_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            rsp_files(&path, files);
+        } else if path.extension().is_some_and(|extension| extension == "rsp") {
+            files.push(path);
+        }
+    }
+}
+
+// Opt-in run against the published NIST files, which are not part of the repository.
+// Unpack shabytetestvectors.zip, shabittestvectors.zip and hmactestvectors.zip from
+// https://csrc.nist.gov/projects/cryptographic-algorithm-validation-program into one
+// directory and run
+//     SHA_CAVP_DIR=<directory> cargo test --test cavp -- --ignored
+// SHA-3 files (SHA3VS) found there are skipped, as the runner only covers FIPS 180.
+#[test]
+#[ignore = "needs the NIST CAVP response files, see SHA_CAVP_DIR"]
+fn nist_files_pass() {
+    let dir = std::env::var("SHA_CAVP_DIR").expect("SHA_CAVP_DIR should name the directory of the NIST .rsp files");
+    let mut files = Vec::new();
+    rsp_files(Path::new(&dir), &mut files);
+    files.retain(|file| {
+        let name = file.file_name().unwrap().to_string_lossy();
+        !name.starts_with("SHA3") && !name.starts_with("SHAKE")
+    });
+    files.sort();
+    assert!(!files.is_empty(), "no SHA or HMAC response files in {}", dir);
+
+    for file in files {
+        let results = cavp::run_file(&file, None).unwrap_or_else(|err| panic!("{}: {}", file.display(), err));
+        assert!(!results.is_empty(), "{}", file.display());
+        let failed: Vec<String> = results.iter()
+            .filter(|result| !result.passed)
+            .map(|result| format!("line {}: {}", result.entry.line, result.entry.vector))
+            .collect();
+        assert!(failed.is_empty(), "{}: {:?}", file.display(), failed);
+
+        // Monte files hold every SHAVS checkpoint, COUNT = 0 to 99
+        let counts: Vec<u32> = results.iter().filter_map(|result| match result.entry.vector {
+            TestVector::Monte { count, .. } => Some(count),
+            _ => None,
+        }).collect();
+        if !counts.is_empty() {
+            assert!(counts.iter().copied().eq(0..cavp::MONTE_CHECKPOINTS as u32), "{}: COUNT {:?}", file.display(), counts);
+        }
+    }
+}
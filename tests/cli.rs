@@ -0,0 +1,156 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_sha_algorithm"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn run_in(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sha_algorithm"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap()
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("sha_algorithm_cli_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn hash_string() {
+    let output = run(&["hash", "--algo", "sha512/224", "--string", "abc"], b"");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa\n");
+    let output = run(&["hash", "--algo", "sha3-256", "--string", "abc"], b"");
+    assert_eq!(stdout(&output), "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532\n");
+}
+
+#[test]
+fn hash_stdin_by_default() {
+    let output = run(&["hash", "-a", "sha1"], b"abc");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "a9993e364706816aba3e25717850c26c9cd0d89d  -\n");
+}
+
+#[test]
+fn missing_file_fails() {
+    let output = run(&["hash", "--string", "abc", "/nonexistent/sha_algorithm/file"], b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\n");
+}
+
+#[test]
+fn usage_errors() {
+    assert_eq!(run(&["hash", "--algo", "md5"], b"").status.code(), Some(2));
+    assert_eq!(run(&["hash", "--algo", "sha512/7"], b"").status.code(), Some(2));
+    assert_eq!(run(&["hash", "--bogus"], b"").status.code(), Some(2));
+    assert_eq!(run(&["hash", "--algo", "sha256", "--length", "64"], b"").status.code(), Some(2));
+    assert_eq!(run(&["hash", "--algo", "shake256", "--length", "12"], b"").status.code(), Some(2));
+    assert_eq!(run(&["hash", "--algo", "shake128/0"], b"").status.code(), Some(2));
+    assert_eq!(run(&["bogus"], b"").status.code(), Some(2));
+}
+
+#[test]
+fn coreutils_output() {
+    let dir = temp_dir("output");
+    std::fs::write(dir.join("a"), b"a").unwrap();
+    let output = run_in(&dir, &["hash", "a"]);
+    assert_eq!(stdout(&output), "ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb  a\n");
+    let output = run_in(&dir, &["hash", "--binary", "--algo", "sha1", "a"]);
+    assert_eq!(stdout(&output), "86f7e437faa5a7fce15d1ddcb9eaeaea377667b8 *a\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn check_lists() {
+    let dir = temp_dir("check");
+    std::fs::write(dir.join("a"), b"a").unwrap();
+    std::fs::write(dir.join("b"), b"b").unwrap();
+    std::fs::write(dir.join("SHA256SUMS"), "\
+ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb  a
+3e23e8160039594a33894f6564e1b1348bbd7a0088d42c4acb73eeaed59c009d *b
+").unwrap();
+    std::fs::write(dir.join("list.sha1"), "SHA1 (a) = 86f7e437faa5a7fce15d1ddcb9eaeaea377667b8\n").unwrap();
+    let output = run_in(&dir, &["hash", "--check", "SHA256SUMS", "list.sha1"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "a: OK\nb: OK\na: OK\n");
+
+    std::fs::write(dir.join("bad.sha512"), format!("{}  a\n{}  missing\n", "0".repeat(128), "0".repeat(128))).unwrap();
+    let output = run_in(&dir, &["hash", "-c", "bad.sha512"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "a: FAILED\nmissing: FAILED open or read\n");
+
+    let output = run_in(&dir, &["hash", "-a", "sha512/224", "a"]);
+    std::fs::write(dir.join("t.txt"), &output.stdout).unwrap();
+    let output = run_in(&dir, &["hash", "-c", "-a", "sha512/224", "t.txt"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    // SHA-512/t lists named after their algorithm, which a file name writes with '_'
+    let output = run_in(&dir, &["hash", "-a", "sha512/256", "a", "b"]);
+    std::fs::write(dir.join("list.sha512_256"), &output.stdout).unwrap();
+    std::fs::write(dir.join("SHA512_256SUMS"), &output.stdout).unwrap();
+    let output = run_in(&dir, &["hash", "-c", "list.sha512_256", "SHA512_256SUMS"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "a: OK\nb: OK\na: OK\nb: OK\n");
+
+    // Otherwise their length says SHA-256, and a mismatch can't be called a failure
+    let output = run_in(&dir, &["hash", "-c", "t.txt"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "a: unknown algorithm\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn cavp_files() {
+    let data = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/cavp");
+    let sha1 = format!("{}/SHA1ShortMsg.rsp", data);
+    let output = run(&["cavp", &sha1, &format!("{}/HMAC.rsp", data)], b"");
+    assert_eq!(output.status.code(), Some(0));
+    let report = stdout(&output);
+    assert!(report.contains(&format!("{}:12: SHA-1 Len = 8: PASS\n", sha1)));
+    assert!(report.contains(&format!("{}: 7 passed, 0 failed\n", sha1)));
+
+    let dir = temp_dir("cavp_files");
+    std::fs::write(dir.join("SHA1ShortMsg.rsp"), "[L = 20]\n\nLen = 8\nMsg = 37\nMD = c1dfd96eea8cc2b62785275bca38ac261256e278\n").unwrap();
+    let output = run_in(&dir, &["cavp", "--quiet", "SHA1ShortMsg.rsp"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "SHA1ShortMsg.rsp:3: SHA-1 Len = 8: FAIL\nSHA1ShortMsg.rsp: 0 passed, 1 failed\n");
+
+    assert_eq!(run(&["cavp"], b"").status.code(), Some(2));
+    assert_eq!(run(&["cavp", "/nonexistent/sha_algorithm/SHA1ShortMsg.rsp"], b"").status.code(), Some(1));
+}
+
+#[test]
+fn trace_formats() {
+    let output = run(&["trace", "-a", "sha1", "--string", "abc"], b"");
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("t= 0: 0116FC33 67452301 7BF36AE2 98BADCFE 10325476\n"));
+    assert!(stdout(&output).ends_with("SHA-1 digest: a9993e364706816aba3e25717850c26c9cd0d89d\n"));
+
+    let output = run(&["trace", "--format=csv"], b"abc");
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).starts_with("block,t,W,a,b,c,d,e,f,g,h\n1,0,61626380,5d6aebcd,"));
+
+    let output = run(&["trace", "-f", "json", "-a", "sha512", "-s", "abc"], b"");
+    assert!(stdout(&output).starts_with("{\"algorithm\":\"SHA-512\",\"word_bits\":64,"));
+
+    assert_eq!(run(&["trace", "-a", "sha3-256", "-s", "abc"], b"").status.code(), Some(1));
+    assert_eq!(run(&["trace", "-f", "xml", "-s", "abc"], b"").status.code(), Some(2));
+    assert_eq!(run(&["trace", "-s", "a", "-s", "b"], b"").status.code(), Some(2));
+}
@@ -0,0 +1,34 @@
+use sha_algorithm::const_hash;
+use sha_algorithm::{hasher, ShaAlgorithm};
+
+// Evaluated by the compiler
+const EMPTY_SHA256: [u8; 32] = const_hash::sha256(b"");
+const ABC_SHA1: [u8; 20] = const_hash::sha1(b"abc");
+const ABC_SHA512: [u8; 64] = const_hash::sha512(b"abc");
+const ASSET: &[u8] = include_bytes!("data/cavp/HMAC.rsp");
+const ASSET_SHA256: [u8; 32] = const_hash::sha256(ASSET);
+
+fn runtime(msg: &[u8], algorithm: ShaAlgorithm) -> Vec<u8> {
+    hasher::hash_bytes(msg, &algorithm).unwrap().get_values()
+}
+
+#[test]
+fn known_answers() {
+    assert_eq!(EMPTY_SHA256.to_vec(), runtime(b"", ShaAlgorithm::SHA256));
+    assert_eq!(EMPTY_SHA256[..4], [0xe3, 0xb0, 0xc4, 0x42]);
+    assert_eq!(ABC_SHA1[..4], [0xa9, 0x99, 0x3e, 0x36]);
+    assert_eq!(ABC_SHA512[..4], [0xdd, 0xaf, 0x35, 0xa1]);
+    assert_eq!(ASSET_SHA256.to_vec(), runtime(ASSET, ShaAlgorithm::SHA256));
+}
+
+// Every length around the one and two block padding boundaries
+#[test]
+fn matches_runtime() {
+    let msg: Vec<u8> = (0..300u32).map(|i| (i * 7 + 3) as u8).collect();
+    for len in 0..msg.len() {
+        let msg = &msg[..len];
+        assert_eq!(const_hash::sha1(msg).to_vec(), runtime(msg, ShaAlgorithm::SHA1), "SHA-1 of {} bytes", len);
+        assert_eq!(const_hash::sha256(msg).to_vec(), runtime(msg, ShaAlgorithm::SHA256), "SHA-256 of {} bytes", len);
+        assert_eq!(const_hash::sha512(msg).to_vec(), runtime(msg, ShaAlgorithm::SHA512), "SHA-512 of {} bytes", len);
+    }
+}
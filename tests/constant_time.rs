@@ -0,0 +1,230 @@
+use std::hint::black_box;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use sha_algorithm::compare::constant_time_eq;
+use sha_algorithm::hmac::{self, Hmac};
+use sha_algorithm::sha2::{Sha2Engine, Sha256};
+use sha_algorithm::sp800_185::Kmac;
+use sha_algorithm::{hasher, sha3, Digest, HashResult, Hasher, Sha1Hasher, Sha256Digest, Sha2Hasher, Sha3Hasher, ShaAlgorithm, ShaError};
+
+const ALGORITHMS: [ShaAlgorithm; 8] = [
+    ShaAlgorithm::SHA1,
+    ShaAlgorithm::SHA256,
+    ShaAlgorithm::SHA384,
+    ShaAlgorithm::SHA512T(256),
+    ShaAlgorithm::SHA3_224,
+    ShaAlgorithm::SHA3_512,
+    ShaAlgorithm::SHAKE128(256),
+    ShaAlgorithm::SHAKE256(1000),
+];
+
+const MSG: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+#[test]
+fn verify_matching_digest() {
+    for algorithm in ALGORITHMS {
+        let expected = hasher::hash_bytes(MSG, &algorithm).unwrap().get_values();
+        let mut hasher = Hasher::new(&algorithm).unwrap();
+        hasher.update(MSG).unwrap();
+        assert!(hasher.verify(&expected).is_ok(), "{}", algorithm);
+    }
+
+    let expected = hasher::hash_bytes(MSG, &ShaAlgorithm::SHA1).unwrap().get_values();
+    let mut sha1 = Sha1Hasher::new();
+    sha1.update(MSG).unwrap();
+    assert!(sha1.verify(&expected).is_ok());
+
+    let expected = hasher::hash_bytes(MSG, &ShaAlgorithm::SHA512T(256)).unwrap().get_values();
+    let mut sha2 = Sha2Hasher::new(&ShaAlgorithm::SHA512T(256)).unwrap();
+    sha2.update(MSG).unwrap();
+    assert!(sha2.verify(&expected).is_ok());
+
+    let expected = hasher::hash_bytes(MSG, &ShaAlgorithm::SHA256).unwrap().get_values();
+    let mut engine = Sha2Engine::<Sha256>::new();
+    engine.update(MSG).unwrap();
+    assert!(engine.verify(&expected).is_ok());
+
+    let expected = sha3::hash_message_bytes(MSG, &ShaAlgorithm::SHA3_256).unwrap().get_values();
+    let mut sha3 = Sha3Hasher::new(&ShaAlgorithm::SHA3_256).unwrap();
+    sha3.update(MSG).unwrap();
+    assert!(sha3.verify(&expected).is_ok());
+}
+
+#[test]
+fn verify_rejects_other_digests() {
+    for algorithm in ALGORITHMS {
+        let expected = hasher::hash_bytes(MSG, &algorithm).unwrap().get_values();
+        let verify = |expected: &[u8]| {
+            let mut hasher = Hasher::new(&algorithm).unwrap();
+            hasher.update(MSG).unwrap();
+            hasher.verify(expected)
+        };
+        for i in [0, expected.len() / 2, expected.len() - 1] {
+            let mut wrong = expected.clone();
+            wrong[i] ^= 0x80;
+            assert!(matches!(verify(&wrong), Err(ShaError::DigestMismatch)), "{} byte {}", algorithm, i);
+        }
+        assert!(matches!(verify(&expected[..expected.len() - 1]), Err(ShaError::DigestMismatch)), "{}", algorithm);
+        assert!(matches!(verify(&[]), Err(ShaError::DigestMismatch)), "{}", algorithm);
+    }
+}
+
+#[test]
+fn result_and_digest_equality() {
+    let a = hasher::hash_bytes(MSG, &ShaAlgorithm::SHA256).unwrap();
+    let b = hasher::hash_bytes(MSG, &ShaAlgorithm::SHA256).unwrap();
+    let other = hasher::hash_bytes(b"other", &ShaAlgorithm::SHA256).unwrap();
+    assert_eq!(a, b);
+    assert_ne!(a, other);
+    assert_ne!(a, hasher::hash_bytes(MSG, &ShaAlgorithm::SHA224).unwrap());
+    assert_eq!(a.digest(), b.digest());
+    assert_ne!(a.digest(), other.digest());
+    assert_eq!(Sha256Digest::try_from(&a).unwrap(), Sha256Digest::try_from(&b).unwrap());
+    assert_ne!(Sha256Digest::try_from(&a).unwrap(), Sha256Digest::try_from(&other).unwrap());
+
+    // Results compare by their bytes, whatever the variant holding them
+    assert_eq!(a, HashResult::U512T(a.get_values()));
+    let shake = hasher::hash_bytes(MSG, &ShaAlgorithm::SHAKE256(1000)).unwrap();
+    assert_eq!(shake, HashResult::U512T(shake.get_values()));
+    assert_ne!(shake, HashResult::U512T(shake.get_values()[..124].to_vec()));
+}
+
+#[test]
+fn mac_equality() {
+    let tag = hmac::hmac(&ShaAlgorithm::SHA256, b"key", MSG).unwrap();
+    assert_eq!(tag, hmac::hmac(&ShaAlgorithm::SHA256, b"key", MSG).unwrap());
+    assert_ne!(tag, hmac::hmac(&ShaAlgorithm::SHA256, b"kez", MSG).unwrap());
+
+    let mut mac = Hmac::new(&ShaAlgorithm::SHA256, b"key").unwrap();
+    mac.update(MSG).unwrap();
+    assert!(mac.clone().verify(&tag.get_values()).is_ok());
+    assert!(matches!(mac.verify(&[0; 32]), Err(ShaError::InvalidMac)));
+}
+
+#[test]
+fn mac_verify_rejects_wrong_lengths() {
+    let mut hmac = Hmac::new(&ShaAlgorithm::SHA256, b"key").unwrap();
+    hmac.update(MSG).unwrap();
+    let tag = hmac.clone().finalize().unwrap().get_values();
+    for wrong in [&[][..], &tag[..1], &tag[..31]] {
+        assert!(matches!(hmac.clone().verify(wrong), Err(ShaError::InvalidMac)), "HMAC, {} bytes", wrong.len());
+    }
+
+    let mut kmac = Kmac::kmac128(b"key", b"").unwrap();
+    kmac.update(MSG).unwrap();
+    let tag = kmac.clone().finalize(32).unwrap();
+    assert!(kmac.clone().verify(&tag, 32).is_ok());
+    for wrong in [&[][..], &tag[..1], &tag[..31]] {
+        assert!(matches!(kmac.clone().verify(wrong, 32), Err(ShaError::InvalidMac)), "KMAC, {} bytes", wrong.len());
+    }
+    let mut flipped = tag.clone();
+    flipped[0] ^= 1;
+    assert!(matches!(kmac.verify(&flipped, 32), Err(ShaError::InvalidMac)));
+}
+
+// Statistical timing harness in the spirit of dudect: the comparison is timed
+// against a secret for two classes of input, one differing in the first byte
+// and one in the last, in random order. An early exit makes the first class
+// faster, which Welch's t-test picks up once enough samples are taken.
+
+const SECRET_LEN: usize = 1024;
+const SAMPLES: usize = 10_000;
+// Calls timed together, so that a sample is well above the clock resolution
+const BATCH: usize = 8;
+// |t| above this is a leak with overwhelming probability
+const THRESHOLD: f64 = 10.0;
+// A leak has to show up in every attempt, so one noisy run cannot fail the test
+const ATTEMPTS: usize = 3;
+
+// Serializes the timing tests, which would otherwise disturb each other
+static TIMING: Mutex<()> = Mutex::new(());
+
+// xorshift64, enough to pick classes and fill buffers
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| self.next() as u8).collect()
+    }
+}
+
+// Welch's t statistic of two sets of timings
+fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+    let stats = |x: &[f64]| {
+        let n = x.len() as f64;
+        let mean = x.iter().sum::<f64>() / n;
+        let var = x.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / (n - 1.0);
+        (n, mean, var)
+    };
+    let (na, ma, va) = stats(a);
+    let (nb, mb, vb) = stats(b);
+    (ma - mb) / (va / na + vb / nb).sqrt()
+}
+
+// The t statistic of one run; `compare` gets an input and checks it against its own secret
+fn measure(inputs: &[Vec<u8>; 2], compare: &dyn Fn(&[u8]) -> bool, rng: &mut Rng) -> f64 {
+    let mut timings = Vec::with_capacity(SAMPLES);
+    for _ in 0..SAMPLES {
+        let class = (rng.next() & 1) as usize;
+        let input = &inputs[class];
+        let start = Instant::now();
+        for _ in 0..BATCH {
+            black_box(compare(black_box(input)));
+        }
+        timings.push((class, start.elapsed().as_nanos() as f64));
+    }
+
+    // Drops the slowest tenth, where preemption and interrupts end up
+    let mut sorted: Vec<f64> = timings.iter().map(|&(_, time)| time).collect();
+    sorted.sort_by(f64::total_cmp);
+    let cutoff = sorted[sorted.len() * 9 / 10];
+    let class = |c: usize| -> Vec<f64> {
+        timings.iter().filter(|&&(class, time)| class == c && time <= cutoff).map(|&(_, time)| time).collect()
+    };
+    welch_t(&class(0), &class(1))
+}
+
+// Whether the running time of `compare` depends on where its input differs from `secret`
+fn leaks(secret: &[u8], compare: impl Fn(&[u8]) -> bool) -> bool {
+    let _guard = TIMING.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut first = secret.to_vec();
+    first[0] ^= 1;
+    let mut last = secret.to_vec();
+    last[secret.len() - 1] ^= 1;
+    let inputs = [first, last];
+    let mut rng = Rng(0x9e3779b97f4a7c15);
+    (0..ATTEMPTS).all(|_| measure(&inputs, &compare, &mut rng).abs() > THRESHOLD)
+}
+
+#[test]
+fn harness_detects_early_exit() {
+    let secret = Rng(1).bytes(SECRET_LEN);
+    assert!(leaks(&secret, |input| input == &secret[..]));
+}
+
+#[test]
+fn constant_time_eq_does_not_leak() {
+    let secret = Rng(2).bytes(SECRET_LEN);
+    assert!(!leaks(&secret, |input| constant_time_eq(input, &secret)));
+}
+
+#[test]
+fn digest_equality_does_not_leak() {
+    let secret = Digest::new(Rng(3).bytes(SECRET_LEN));
+    assert!(!leaks(secret.as_bytes(), |input| Digest::new(input.to_vec()) == secret));
+}
+
+#[test]
+fn result_equality_does_not_leak() {
+    let secret = Rng(4).bytes(SECRET_LEN);
+    let expected = HashResult::U512T(secret.clone());
+    assert!(!leaks(&secret, |input| HashResult::U512T(input.to_vec()) == expected));
+}
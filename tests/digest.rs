@@ -0,0 +1,28 @@
+use sha_algorithm::sha2;
+use sha_algorithm::{Digest, ShaAlgorithm, ShaError};
+
+#[test]
+fn bytes_keep_their_leading_zeros() {
+    // SHA-256("abc") has the bytes 0x01 and 0x00 in it
+    let digest = sha2::hash_message("abc", &ShaAlgorithm::SHA256).unwrap().digest();
+    assert_eq!(digest.to_string(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    assert_eq!(format!("{:x}", digest), digest.to_string());
+    assert_eq!(format!("{:X}", digest), "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD");
+    assert_eq!(format!("{:#x}", Digest::new(vec![0x0a, 0x00])), "0x0a00");
+    assert_eq!(digest.len(), 32);
+}
+
+#[test]
+fn parse_round_trip() {
+    let digest = sha2::hash_message("abc", &ShaAlgorithm::SHA512T(224)).unwrap().digest();
+    assert_eq!(digest.to_string().parse::<Digest>().unwrap(), digest);
+    assert_eq!(format!("{:X}", digest).parse::<Digest>().unwrap(), digest);
+    assert_eq!("0a0B".parse::<Digest>().unwrap().as_bytes(), &[0x0a, 0x0b]);
+}
+
+#[test]
+fn parse_rejects_malformed_hex() {
+    for bad in ["", "a", "abc", "zz", "0x0a", "+a", "a b "] {
+        assert!(matches!(bad.parse::<Digest>(), Err(ShaError::InvalidDigest)), "{:?}", bad);
+    }
+}
@@ -0,0 +1,37 @@
+use sha_algorithm::sha2::{self, Sha2Descriptor, Sha2Engine, Sha224, Sha256, Sha384, Sha512};
+use sha_algorithm::types::wrappers::MessageBlock;
+use sha_algorithm::{ShaAlgorithm, ShaError};
+
+fn engine_digest<A: Sha2Descriptor>(msg: &[u8]) -> String {
+    let mut engine = Sha2Engine::<A>::new();
+    for part in msg.chunks(37) {
+        engine.update(part).unwrap();
+    }
+    engine.finalize().unwrap().digest().to_string()
+}
+
+#[test]
+fn typed_engines_match_the_hasher() {
+    let msg: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+    let hasher = |algorithm| sha2::hash_message_bytes(&msg, &algorithm).unwrap().digest().to_string();
+    assert_eq!(engine_digest::<Sha224>(&msg), hasher(ShaAlgorithm::SHA224));
+    assert_eq!(engine_digest::<Sha256>(&msg), hasher(ShaAlgorithm::SHA256));
+    assert_eq!(engine_digest::<Sha384>(&msg), hasher(ShaAlgorithm::SHA384));
+    assert_eq!(engine_digest::<Sha512>(&msg), hasher(ShaAlgorithm::SHA512));
+}
+
+#[test]
+fn typed_engine_known_answer() {
+    assert_eq!(engine_digest::<Sha256>(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    assert_eq!(engine_digest::<Sha384>(b""),
+        "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da274edebfe76f65fbd51ad2f14898b95b");
+}
+
+#[test]
+fn mismatched_block_size() {
+    let small = [MessageBlock::Block512([0; 16])];
+    let large = [MessageBlock::Block1024([0; 16])];
+    assert!(matches!(sha2::hash(&small, ShaAlgorithm::SHA512), Err(ShaError::InvalidPadding)));
+    assert!(matches!(sha2::hash(&large, ShaAlgorithm::SHA256), Err(ShaError::InvalidPadding)));
+    assert!(sha2::hash(&large, ShaAlgorithm::SHA512T(256)).is_ok());
+}
@@ -0,0 +1,105 @@
+use std::collections::{BTreeSet, HashMap};
+
+use sha_algorithm::sha2::{Sha2Engine, Sha224, Sha256, Sha512};
+use sha_algorithm::{hasher, sha1, sha2, sha3, HashResult, ShaAlgorithm, ShaError};
+use sha_algorithm::{Sha1Digest, Sha1Hasher, Sha224Digest, Sha256Digest, Sha384Digest, Sha512Digest};
+use sha_algorithm::{Sha3_256Digest, Sha512_224Digest, Sha512_256Digest};
+
+const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+#[test]
+fn conversions_from_results() {
+    let result = sha2::hash_message("abc", &ShaAlgorithm::SHA256).unwrap();
+    let digest = Sha256Digest::try_from(&result).unwrap();
+    assert_eq!(digest.to_string(), ABC_SHA256);
+    assert_eq!(digest.as_ref(), &result.get_values()[..]);
+
+    let sha1 = Sha1Digest::try_from(sha1::hash_message("abc", &ShaAlgorithm::SHA1).unwrap()).unwrap();
+    assert_eq!(sha1.to_string(), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    let truncated = Sha512_224Digest::try_from(sha2::hash_message("abc", &ShaAlgorithm::SHA512T(224)).unwrap()).unwrap();
+    assert_eq!(truncated.to_string(), "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa");
+    let sha3 = Sha3_256Digest::try_from(sha3::hash_message("abc", &ShaAlgorithm::SHA3_256).unwrap()).unwrap();
+    assert_eq!(sha3.to_string(), "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532");
+}
+
+#[test]
+fn conversions_back_to_results() {
+    let msg = b"The quick brown fox jumps over the lazy dog";
+    for algorithm in [ShaAlgorithm::SHA1, ShaAlgorithm::SHA224, ShaAlgorithm::SHA256, ShaAlgorithm::SHA384,
+        ShaAlgorithm::SHA512, ShaAlgorithm::SHA512T(224), ShaAlgorithm::SHA512T(256), ShaAlgorithm::SHA3_256] {
+        let result = hasher::hash_bytes(msg, &algorithm).unwrap();
+        let round_trip: HashResult = match algorithm {
+            ShaAlgorithm::SHA1 => Sha1Digest::try_from(&result).unwrap().into(),
+            ShaAlgorithm::SHA224 => Sha224Digest::try_from(&result).unwrap().into(),
+            ShaAlgorithm::SHA256 => Sha256Digest::try_from(&result).unwrap().into(),
+            ShaAlgorithm::SHA384 => Sha384Digest::try_from(&result).unwrap().into(),
+            ShaAlgorithm::SHA512 => Sha512Digest::try_from(&result).unwrap().into(),
+            ShaAlgorithm::SHA512T(224) => Sha512_224Digest::try_from(&result).unwrap().into(),
+            ShaAlgorithm::SHA512T(_) => Sha512_256Digest::try_from(&result).unwrap().into(),
+            _ => Sha3_256Digest::try_from(&result).unwrap().into(),
+        };
+        assert_eq!(std::mem::discriminant(&round_trip), std::mem::discriminant(&result), "{}", algorithm);
+        assert_eq!(round_trip.digest(), result.digest(), "{}", algorithm);
+    }
+}
+
+#[test]
+fn typed_finalize() {
+    let mut engine = Sha2Engine::<Sha256>::new();
+    engine.update(b"abc").unwrap();
+    assert_eq!(engine.finalize_fixed().unwrap().to_string(), ABC_SHA256);
+
+    let mut engine = Sha2Engine::<Sha224>::new();
+    engine.update(b"abc").unwrap();
+    let expected = sha2::hash_message("abc", &ShaAlgorithm::SHA224).unwrap();
+    assert_eq!(engine.finalize_fixed().unwrap(), Sha224Digest::try_from(expected).unwrap());
+
+    let engine = Sha2Engine::<Sha512>::new();
+    let expected = sha2::hash_message("", &ShaAlgorithm::SHA512).unwrap();
+    assert_eq!(engine.finalize_fixed().unwrap(), Sha512Digest::try_from(expected).unwrap());
+
+    let mut hasher = Sha1Hasher::new();
+    hasher.update(b"abc").unwrap();
+    assert_eq!(hasher.finalize_fixed().unwrap().to_string(), "a9993e364706816aba3e25717850c26c9cd0d89d");
+}
+
+#[test]
+fn wrong_lengths() {
+    let result = sha2::hash_message("abc", &ShaAlgorithm::SHA384).unwrap();
+    assert!(matches!(Sha256Digest::try_from(&result), Err(ShaError::InvalidDigestLength(48))));
+    assert!(matches!(Sha1Digest::try_from(&[0u8; 21][..]), Err(ShaError::InvalidDigestLength(21))));
+    assert!(matches!(Sha1Digest::try_from(&[][..]), Err(ShaError::InvalidDigestLength(0))));
+    assert!(matches!(ABC_SHA256[2..].parse::<Sha256Digest>(), Err(ShaError::InvalidDigest)));
+    assert!(matches!(format!("{}00", ABC_SHA256).parse::<Sha256Digest>(), Err(ShaError::InvalidDigest)));
+    assert!(matches!(ABC_SHA256.replace('b', "g").parse::<Sha256Digest>(), Err(ShaError::InvalidDigest)));
+}
+
+#[test]
+fn formatting_and_parsing() {
+    let digest: Sha256Digest = ABC_SHA256.parse().unwrap();
+    assert_eq!(digest, ABC_SHA256.to_uppercase().parse().unwrap());
+    assert_eq!(format!("{:X}", digest), ABC_SHA256.to_uppercase());
+    assert_eq!(format!("{:#x}", digest), format!("0x{}", ABC_SHA256));
+    assert_eq!(format!("{:?}", digest), format!("Sha256Digest({})", ABC_SHA256));
+    assert_eq!(sha_algorithm::Digest::from(digest).to_string(), ABC_SHA256);
+}
+
+#[test]
+fn map_keys() {
+    let digests: Vec<Sha256Digest> = (0..100u32)
+        .map(|i| Sha256Digest::try_from(sha2::hash_message_bytes(&i.to_be_bytes(), &ShaAlgorithm::SHA256).unwrap()).unwrap())
+        .collect();
+    let index: HashMap<Sha256Digest, usize> = digests.iter().enumerate().map(|(i, &digest)| (digest, i)).collect();
+    assert_eq!(index.len(), 100);
+    for (i, digest) in digests.iter().enumerate() {
+        assert_eq!(index[digest], i);
+        // Lookup by the raw bytes, through `Borrow<[u8]>`
+        assert_eq!(index.get(&digest.into_bytes()[..]), Some(&i));
+    }
+
+    let ordered: BTreeSet<Sha256Digest> = digests.iter().copied().collect();
+    let mut bytes: Vec<[u8; 32]> = digests.iter().map(|digest| digest.into_bytes()).collect();
+    bytes.sort();
+    assert!(ordered.iter().map(|digest| digest.into_bytes()).eq(bytes));
+    assert_eq!(std::mem::size_of::<Sha256Digest>(), 32);
+}
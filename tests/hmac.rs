@@ -0,0 +1,207 @@
+use sha_algorithm::hmac::{self, constant_time_eq, Hmac};
+use sha_algorithm::{ShaAlgorithm, ShaError};
+
+struct Vector {
+    key: &'static [u8],
+    data: &'static [u8],
+    tags: &'static [(ShaAlgorithm, &'static str)],
+}
+
+fn check(vectors: &[Vector]) {
+    for (i, vector) in vectors.iter().enumerate() {
+        for (algorithm, tag) in vector.tags.iter() {
+            let mac = hmac::hmac(algorithm, vector.key, vector.data).unwrap().digest().to_string();
+            // Test case 5 of RFC 4231 only lists a truncated tag
+            assert_eq!(&mac[..tag.len()], *tag, "test case {} {:?}", i + 1, algorithm);
+        }
+    }
+}
+
+const RFC_4231: &[Vector] = &[
+    Vector {
+        key: &[0x0b; 20],
+        data: b"Hi There",
+        tags: &[
+            (ShaAlgorithm::SHA224, "896fb1128abbdf196832107cd49df33f47b4b1169912ba4f53684b22"),
+            (ShaAlgorithm::SHA256, "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"),
+            (ShaAlgorithm::SHA384, "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59cfaea9ea9076ede7f4af152e8b2fa9cb6"),
+            (ShaAlgorithm::SHA512, "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"),
+        ],
+    },
+    Vector {
+        key: b"Jefe",
+        data: b"what do ya want for nothing?",
+        tags: &[
+            (ShaAlgorithm::SHA224, "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44"),
+            (ShaAlgorithm::SHA256, "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"),
+            (ShaAlgorithm::SHA384, "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649"),
+            (ShaAlgorithm::SHA512, "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"),
+        ],
+    },
+    Vector {
+        key: &[0xaa; 20],
+        data: &[0xdd; 50],
+        tags: &[
+            (ShaAlgorithm::SHA224, "7fb3cb3588c6c1f6ffa9694d7d6ad2649365b0c1f65d69d1ec8333ea"),
+            (ShaAlgorithm::SHA256, "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe"),
+            (ShaAlgorithm::SHA384, "88062608d3e6ad8a0aa2ace014c8a86f0aa635d947ac9febe83ef4e55966144b2a5ab39dc13814b94e3ab6e101a34f27"),
+            (ShaAlgorithm::SHA512, "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb"),
+        ],
+    },
+    Vector {
+        key: &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19],
+        data: &[0xcd; 50],
+        tags: &[
+            (ShaAlgorithm::SHA224, "6c11506874013cac6a2abc1bb382627cec6a90d86efc012de7afec5a"),
+            (ShaAlgorithm::SHA256, "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b"),
+            (ShaAlgorithm::SHA384, "3e8a69b7783c25851933ab6290af6ca77a9981480850009cc5577c6e1f573b4e6801dd23c4a7d679ccf8a386c674cffb"),
+            (ShaAlgorithm::SHA512, "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3dba91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd"),
+        ],
+    },
+    Vector {
+        key: &[0x0c; 20],
+        data: b"Test With Truncation",
+        tags: &[
+            (ShaAlgorithm::SHA224, "0e2aea68a90c8d37c988bcdb9fca6fa8"),
+            (ShaAlgorithm::SHA256, "a3b6167473100ee06e0c796c2955552b"),
+            (ShaAlgorithm::SHA384, "3abf34c3503b2a23a46efc619baef897"),
+            (ShaAlgorithm::SHA512, "415fad6271580a531d4179bc891d87a6"),
+        ],
+    },
+    Vector {
+        key: &[0xaa; 131],
+        data: b"Test Using Larger Than Block-Size Key - Hash Key First",
+        tags: &[
+            (ShaAlgorithm::SHA224, "95e9a0db962095adaebe9b2d6f0dbce2d499f112f2d2b7273fa6870e"),
+            (ShaAlgorithm::SHA256, "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"),
+            (ShaAlgorithm::SHA384, "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c60c2ef6ab4030fe8296248df163f44952"),
+            (ShaAlgorithm::SHA512, "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"),
+        ],
+    },
+    Vector {
+        key: &[0xaa; 131],
+        data: b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm.",
+        tags: &[
+            (ShaAlgorithm::SHA224, "3a854166ac5d9f023f54d517d0b39dbd946770db9c2b95c9f6f565d1"),
+            (ShaAlgorithm::SHA256, "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2"),
+            (ShaAlgorithm::SHA384, "6617178e941f020d351e2f254e8fd32c602420feb0b8fb9adccebb82461e99c5a678cc31e799176d3860e6110c46523e"),
+            (ShaAlgorithm::SHA512, "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58"),
+        ],
+    },
+];
+
+const RFC_2202: &[Vector] = &[
+    Vector {
+        key: &[0x0b; 20],
+        data: b"Hi There",
+        tags: &[
+            (ShaAlgorithm::SHA1, "b617318655057264e28bc0b6fb378c8ef146be00"),
+        ],
+    },
+    Vector {
+        key: b"Jefe",
+        data: b"what do ya want for nothing?",
+        tags: &[
+            (ShaAlgorithm::SHA1, "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"),
+        ],
+    },
+    Vector {
+        key: &[0xaa; 20],
+        data: &[0xdd; 50],
+        tags: &[
+            (ShaAlgorithm::SHA1, "125d7342b9ac11cd91a39af48aa17b4f63f175d3"),
+        ],
+    },
+    Vector {
+        key: &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19],
+        data: &[0xcd; 50],
+        tags: &[
+            (ShaAlgorithm::SHA1, "4c9007f4026250c6bc8414f9bf50c86c2d7235da"),
+        ],
+    },
+    Vector {
+        key: &[0x0c; 20],
+        data: b"Test With Truncation",
+        tags: &[
+            (ShaAlgorithm::SHA1, "4c1a03424b55e07fe7f27be1d58bb9324a9a5a04"),
+        ],
+    },
+    Vector {
+        key: &[0xaa; 80],
+        data: b"Test Using Larger Than Block-Size Key - Hash Key First",
+        tags: &[
+            (ShaAlgorithm::SHA1, "aa4ae5e15272d00e95705637ce8a3b55ed402112"),
+        ],
+    },
+    Vector {
+        key: &[0xaa; 80],
+        data: b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data",
+        tags: &[
+            (ShaAlgorithm::SHA1, "e8e99d0f45237d786d6bbaa7965c7808bbff1a91"),
+        ],
+    },
+];
+
+#[test]
+fn rfc_4231_sha2() {
+    check(RFC_4231);
+}
+
+#[test]
+fn rfc_2202_sha1() {
+    check(RFC_2202);
+}
+
+#[test]
+fn sha512_t_uses_128_byte_blocks() {
+    let mac = hmac::hmac(&ShaAlgorithm::SHA512T(224), b"Jefe", b"what do ya want for nothing?").unwrap();
+    assert_eq!(mac.digest().to_string(), "4a530b31a79ebcce36916546317c45f247d83241dfb818fd37254bde");
+    let mac = hmac::hmac(&ShaAlgorithm::SHA512T(256), b"Jefe", b"what do ya want for nothing?").unwrap();
+    assert_eq!(mac.digest().to_string(), "6df7b24630d5ccb2ee335407081a87188c221489768fa2020513b2d593359456");
+    let mac = hmac::hmac(&ShaAlgorithm::SHA512T(256), RFC_4231[6].key, RFC_4231[6].data).unwrap();
+    assert_eq!(mac.digest().to_string(), "6ea83f8e7315072c0bdaa33b93a26fc1659974637a9db8a887d06c05a7f35a66");
+}
+
+#[test]
+fn streaming_matches_one_shot() {
+    let data = RFC_4231[6].data;
+    for algorithm in [ShaAlgorithm::SHA1, ShaAlgorithm::SHA256, ShaAlgorithm::SHA512T(224)] {
+        let expected = hmac::hmac(&algorithm, b"key", data).unwrap().get_values();
+        for chunk in [1, 13, 64, 129] {
+            let mut mac = Hmac::new(&algorithm, b"key").unwrap();
+            for part in data.chunks(chunk) {
+                mac.update(part).unwrap();
+            }
+            assert_eq!(mac.finalize().unwrap().get_values(), expected);
+        }
+    }
+}
+
+#[test]
+fn verify_tags() {
+    let tag = hmac::hmac(&ShaAlgorithm::SHA256, b"Jefe", b"what do ya want for nothing?").unwrap().get_values();
+    let mut mac = Hmac::new(&ShaAlgorithm::SHA256, b"Jefe").unwrap();
+    mac.update(b"what do ya want for nothing?").unwrap();
+    assert!(mac.clone().verify(&tag).is_ok());
+
+    let mut forged = tag.clone();
+    forged[31] ^= 1;
+    assert!(matches!(mac.clone().verify(&forged), Err(ShaError::InvalidMac)));
+    assert!(matches!(mac.verify(&tag[..16]), Err(ShaError::InvalidMac)));
+}
+
+#[test]
+fn shake_is_rejected() {
+    for algorithm in [ShaAlgorithm::SHAKE128(256), ShaAlgorithm::SHAKE256(512)] {
+        assert!(matches!(Hmac::new(&algorithm, b"key"), Err(ShaError::InvalidAlgorithm)), "{}", algorithm);
+        assert!(matches!(hmac::hmac(&algorithm, b"key", b"msg"), Err(ShaError::InvalidAlgorithm)), "{}", algorithm);
+    }
+}
+
+#[test]
+fn constant_time_comparison() {
+    assert!(constant_time_eq(b"", b""));
+    assert!(constant_time_eq(b"abc", b"abc"));
+    assert!(!constant_time_eq(b"abc", b"abd"));
+    assert!(!constant_time_eq(b"abc", b"ab"));
+}
@@ -0,0 +1,112 @@
+// Uses only the API that is left without the `std` feature, so it also runs under
+// `cargo test --no-default-features --test no_std`
+use sha_algorithm::sha2::{Sha2Engine, Sha384};
+use sha_algorithm::{const_hash, sha1, sha2, HashResult, Sha1Digest, Sha1Hasher, Sha256Digest, Sha2Hasher, ShaAlgorithm, ShaError};
+
+fn message() -> [u8; 200] {
+    let mut msg = [0u8; 200];
+    for (i, byte) in msg.iter_mut().enumerate() {
+        *byte = (i * 13 + 5) as u8;
+    }
+    msg
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// Digest of `message()` through a hasher fed in uneven chunks
+fn finalize_into(algorithm: ShaAlgorithm) -> String {
+    let mut out = [0u8; 64];
+    let len = match algorithm {
+        ShaAlgorithm::SHA1 => {
+            let mut hasher = Sha1Hasher::new();
+            for chunk in message().chunks(37) {
+                hasher.update(chunk).unwrap();
+            }
+            hasher.finalize_into(&mut out).unwrap()
+        },
+        _ => {
+            let mut hasher = Sha2Hasher::new(&algorithm).unwrap();
+            for chunk in message().chunks(37) {
+                hasher.update(chunk).unwrap();
+            }
+            hasher.finalize_into(&mut out).unwrap()
+        },
+    };
+    hex(&out[..len])
+}
+
+#[test]
+fn digests_without_allocation() {
+    let cases = [
+        (ShaAlgorithm::SHA1, "4985b65ac12064147211642711bc8e84f51fd936"),
+        (ShaAlgorithm::SHA224, "db808eda5b4decd5c4363e6bca3285e0353b680b35efc118b8be37d9"),
+        (ShaAlgorithm::SHA256, "5662cd43a9a08890f6eea10b9cb37854163d54629a5ca604a03e88c3ce47419f"),
+        (ShaAlgorithm::SHA384, "21bc2e876567279ffce6d480fce797259c0d07188cd076e27d8740277c5aea92fb98f98e42c2029a7011d304561660cd"),
+        (ShaAlgorithm::SHA512, "c4925b54348a7d9566db90fe2bcc76455621dd288796258965514a9dae3985a9380b7e691ec7bda7b9e10255c1b3c775c9201d39c598d5090917b469a0dd9f82"),
+        (ShaAlgorithm::SHA512T(224), "2d9829874373b2b64b16ad1ca45bdf9985d79d04384577aa98055c63"),
+        (ShaAlgorithm::SHA512T(256), "574350892b1b15d42dc5b6fa5f7d9fdc0b07aee5d2c44f0a396177d592a3ce17"),
+        // One and two digit t in the string that derives the initial values
+        (ShaAlgorithm::SHA512T(8), "0c"),
+        (ShaAlgorithm::SHA512T(40), "21f0230e02"),
+        (ShaAlgorithm::SHA512T(96), "41fc589a39b10691f62adf6a"),
+    ];
+    for (algorithm, expected) in cases {
+        assert_eq!(finalize_into(algorithm), expected, "{}", algorithm);
+    }
+}
+
+#[test]
+fn one_shot_results() {
+    let msg = message();
+    match sha1::hash_message_bytes(&msg, &ShaAlgorithm::SHA1).unwrap() {
+        HashResult::U160(words) => assert_eq!(words.get_values()[0], 0x4985b65a),
+        other => panic!("unexpected result {:?}", other),
+    }
+    match sha2::hash_message_bytes(&msg, &ShaAlgorithm::SHA256).unwrap() {
+        HashResult::U256(words) => assert_eq!(words.get_values()[7], 0xce47419f),
+        other => panic!("unexpected result {:?}", other),
+    }
+    match sha2::hash_message_bits(&[0x68], 5, &ShaAlgorithm::SHA256).unwrap() {
+        HashResult::U256(words) => assert_eq!(words.get_values()[0], 0xd6d3e02a),
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(const_hash::sha256(&msg)[..4], [0x56, 0x62, 0xcd, 0x43]);
+}
+
+#[test]
+fn fixed_digests() {
+    let msg = message();
+    let mut hasher = Sha1Hasher::new();
+    hasher.update(&msg).unwrap();
+    let digest = hasher.finalize_fixed().unwrap();
+    assert_eq!(digest.as_bytes()[..4], [0x49, 0x85, 0xb6, 0x5a]);
+    assert_eq!(Sha1Digest::try_from(&sha1::hash_message_bytes(&msg, &ShaAlgorithm::SHA1).unwrap()).unwrap(), digest);
+
+    let result = sha2::hash_message_bytes(&msg, &ShaAlgorithm::SHA256).unwrap();
+    assert_eq!(Sha256Digest::try_from(&result).unwrap(), Sha256Digest::new(const_hash::sha256(&msg)));
+
+    let mut engine = Sha2Engine::<Sha384>::new();
+    engine.update(&msg).unwrap();
+    assert_eq!(engine.finalize_fixed().unwrap().as_bytes().len(), 48);
+}
+
+#[test]
+fn errors() {
+    let mut short = [0u8; 31];
+    assert!(matches!(Sha2Hasher::new(&ShaAlgorithm::SHA256).unwrap().finalize_into(&mut short), Err(ShaError::InvalidOutputLength(31))));
+    assert!(matches!(Sha2Hasher::new(&ShaAlgorithm::SHA512T(12)), Err(ShaError::InvalidTruncation(12))));
+    // FIPS 180-4 only defines t < 512, and leaves out 384
+    for t in [0, 384, 512, 520] {
+        assert!(matches!(Sha2Hasher::new(&ShaAlgorithm::SHA512T(t)), Err(ShaError::InvalidTruncation(x)) if x == t), "t = {}", t);
+    }
+    assert!(Sha2Hasher::new(&ShaAlgorithm::SHA512T(504)).is_ok());
+    assert!(matches!(sha2::hash_message_bytes(b"abc", &ShaAlgorithm::SHA1), Err(ShaError::InvalidAlgorithm)));
+
+    let mut hasher = Sha1Hasher::new();
+    hasher.update_bits(&[0x80], 1).unwrap();
+    assert!(matches!(hasher.update(b"more"), Err(ShaError::InvalidState(_))));
+    assert!("sha-512/224".parse::<ShaAlgorithm>().is_ok());
+    assert!("a-very-long-name-that-fits-no-algorithm".parse::<ShaAlgorithm>().is_err());
+}
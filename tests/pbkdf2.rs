@@ -0,0 +1,46 @@
+use sha_algorithm::pbkdf2::pbkdf2;
+use sha_algorithm::{Digest, ShaAlgorithm, ShaError};
+
+fn derive(algorithm: ShaAlgorithm, password: &[u8], salt: &[u8], iterations: u32, length: usize) -> String {
+    Digest::new(pbkdf2(&algorithm, password, salt, iterations, length).unwrap()).to_string()
+}
+
+// RFC 6070, PBKDF2-HMAC-SHA1
+#[test]
+fn rfc_6070() {
+    assert_eq!(derive(ShaAlgorithm::SHA1, b"password", b"salt", 1, 20), "0c60c80f961f0e71f3a9b524af6012062fe037a6");
+    assert_eq!(derive(ShaAlgorithm::SHA1, b"password", b"salt", 2, 20), "ea6c014dc72d6f8ccd1ed92ace1d41f0d8de8957");
+    assert_eq!(derive(ShaAlgorithm::SHA1, b"password", b"salt", 4096, 20), "4b007901b765489abead49d926f721d065a429c1");
+    assert_eq!(derive(ShaAlgorithm::SHA1, b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 4096, 25),
+        "3d2eec4fe41c849b80c8d83662c0e44a8b291a964cf2f07038");
+    assert_eq!(derive(ShaAlgorithm::SHA1, b"pass\0word", b"sa\0lt", 4096, 16), "56fa6aa75548099dcc37d7f03425e0c3");
+}
+
+#[test]
+#[ignore = "16777216 iterations; run with --ignored"]
+fn rfc_6070_long() {
+    assert_eq!(derive(ShaAlgorithm::SHA1, b"password", b"salt", 16777216, 20), "eefe3d61cd4da4e4e9945b3d6ba2158c2634e984");
+}
+
+// RFC 7914 section 11, PBKDF2-HMAC-SHA256
+#[test]
+fn rfc_7914() {
+    assert_eq!(derive(ShaAlgorithm::SHA256, b"passwd", b"salt", 1, 64),
+        "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783");
+    assert_eq!(derive(ShaAlgorithm::SHA256, b"Password", b"NaCl", 80000, 64),
+        "4ddcd8f60b98be21830cee5ef22701f9641a4418d04c0414aeff08876b34ab56a1d425a1225833549adb841b51c9b3176a272bdebba1d078478f62b397f33c8d");
+}
+
+#[test]
+fn sha512_prf() {
+    assert_eq!(derive(ShaAlgorithm::SHA512, b"password", b"salt", 1000, 64),
+        "afe6c5530785b6cc6b1c6453384731bd5ee432ee549fd42fb6695779ad8a1c5bf59de69c48f774efc4007d5298f9033c0241d5ab69305e7b64eceeb8d834cfec");
+    // Spans three output blocks, the last one truncated
+    assert_eq!(derive(ShaAlgorithm::SHA512, b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 4096, 150),
+        "8c0511f4c6e597c6ac6315d8f0362e225f3c501495ba23b868c005174dc4ee71115b59f9e60cd9532fa33e0f75aefe30225c583a186cd82bd4daea9724a3d3b804f75bdd41494fa324cab24bcc680fb3b96a30cf5d21fac3c2875913919f3399b1d9ce7eb54c95ba49118596cf7465719bbe02c4ecab1b1541298c321d13c6f6d414c28163b051a1d313cec13a76ebdbba624eb2c742");
+}
+
+#[test]
+fn rejects_zero_iterations() {
+    assert!(matches!(pbkdf2(&ShaAlgorithm::SHA256, b"p", b"s", 0, 32), Err(ShaError::InvalidIterationCount)));
+}
@@ -0,0 +1,93 @@
+use sha_algorithm::hasher::Hasher;
+use sha_algorithm::sha3::{self, keccak_f1600, Sha3Hasher, Sponge};
+use sha_algorithm::{ShaAlgorithm, ShaError};
+
+fn sha3(msg: &[u8], algorithm: ShaAlgorithm) -> String {
+    sha3::hash_message_bytes(msg, &algorithm).unwrap().digest().to_string()
+}
+
+// NIST SHA-3 examples: empty message, "abc", 1600 bits of 0xa3 and the 448-bit message
+#[test]
+fn nist_examples() {
+    assert_eq!(sha3(b"", ShaAlgorithm::SHA3_224),
+        "6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7");
+    assert_eq!(sha3(b"", ShaAlgorithm::SHA3_256),
+        "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a");
+    assert_eq!(sha3(b"", ShaAlgorithm::SHA3_384),
+        "0c63a75b845e4f7d01107d852e4c2485c51a50aaaa94fc61995e71bbee983a2ac3713831264adb47fb6bd1e058d5f004");
+    assert_eq!(sha3(b"", ShaAlgorithm::SHA3_512),
+        "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26");
+    assert_eq!(sha3(b"abc", ShaAlgorithm::SHA3_224),
+        "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf");
+    assert_eq!(sha3(b"abc", ShaAlgorithm::SHA3_256),
+        "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532");
+    assert_eq!(sha3(b"abc", ShaAlgorithm::SHA3_384),
+        "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25");
+    assert_eq!(sha3(b"abc", ShaAlgorithm::SHA3_512),
+        "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0");
+    assert_eq!(sha3(&[0xa3; 200], ShaAlgorithm::SHA3_224),
+        "9376816aba503f72f96ce7eb65ac095deee3be4bf9bbc2a1cb7e11e0");
+    assert_eq!(sha3(&[0xa3; 200], ShaAlgorithm::SHA3_256),
+        "79f38adec5c20307a98ef76e8324afbfd46cfd81b22e3973c65fa1bd9de31787");
+    assert_eq!(sha3(&[0xa3; 200], ShaAlgorithm::SHA3_384),
+        "1881de2ca7e41ef95dc4732b8f5f002b189cc1e42b74168ed1732649ce1dbcdd76197a31fd55ee989f2d7050dd473e8f");
+    assert_eq!(sha3(&[0xa3; 200], ShaAlgorithm::SHA3_512),
+        "e76dfad22084a8b1467fcf2ffa58361bec7628edf5f3fdc0e4805dc48caeeca81b7c13c30adf52a3659584739a2df46be589c51ca1a4a8416df6545a1ce8ba00");
+    assert_eq!(sha3(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", ShaAlgorithm::SHA3_224),
+        "8a24108b154ada21c9fd5574494479ba5c7e7ab76ef264ead0fcce33");
+    assert_eq!(sha3(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", ShaAlgorithm::SHA3_256),
+        "41c0dba2a9d6240849100376a8235e2c82e1b9998a999e21db32dd97496d3376");
+    assert_eq!(sha3(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", ShaAlgorithm::SHA3_384),
+        "991c665755eb3a4b6bbdfb75c78a492e8c56a22c5c4d7e429bfdbc32b9d4ad5aa04a1f076e62fea19eef51acd0657c22");
+    assert_eq!(sha3(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", ShaAlgorithm::SHA3_512),
+        "04a371e84ecfb5b8b77cb48610fca8182dd457ce6f326a0fd3d7ec2f1e91636dee691fbe0c985302ba1b0d8dc78c086346b533b49c030d99a27daf1139d6e75e");
+}
+
+#[test]
+fn streaming_matches_one_shot() {
+    let msg = [0xa3; 200];
+    for algorithm in [ShaAlgorithm::SHA3_224, ShaAlgorithm::SHA3_256, ShaAlgorithm::SHA3_384, ShaAlgorithm::SHA3_512] {
+        let expected = sha3(&msg, algorithm);
+        for chunk in [1, 7, 71, 72, 73, 136, 199] {
+            let mut hasher = Sha3Hasher::new(&algorithm).unwrap();
+            for part in msg.chunks(chunk) {
+                hasher.update(part).unwrap();
+            }
+            assert_eq!(hasher.finalize().unwrap().digest().to_string(), expected, "{:?} chunk {}", algorithm, chunk);
+        }
+    }
+}
+
+#[test]
+fn runtime_hasher_and_names() {
+    let mut hasher = Hasher::new(&"sha3-256".parse().unwrap()).unwrap();
+    hasher.update(b"abc").unwrap();
+    assert_eq!(hasher.finalize().unwrap().digest().to_string(), sha3(b"abc", ShaAlgorithm::SHA3_256));
+    assert_eq!(ShaAlgorithm::SHA3_384.to_string(), "SHA3-384");
+    assert!(sha3::hash_message("abc", &ShaAlgorithm::SHA256).is_err());
+}
+
+// Keccak-f[1600] applied to the all-zero state, from the Keccak team's KeccakF-1600 intermediate values
+#[test]
+fn permutation_of_zero_state() {
+    let mut state = [0u64; 25];
+    keccak_f1600(&mut state);
+    assert_eq!(state[0], 0xF1258F7940E1DDE7);
+    assert_eq!(state[1], 0x84D5CCF933C0478A);
+    assert_eq!(state[24], 0xEAF1FF7B5CECA249);
+}
+
+#[test]
+fn sponge_errors() {
+    for rate in [0, 12, 200, 208] {
+        assert!(matches!(Sponge::new(rate), Err(ShaError::InvalidRate(r)) if r == rate), "rate {}", rate);
+    }
+
+    let mut sponge = Sponge::new(136).unwrap();
+    assert!(matches!(sponge.squeeze(&mut [0; 8]), Err(ShaError::InvalidState(_))));
+    sponge.absorb(b"abc").unwrap();
+    sponge.pad(0x06).unwrap();
+    assert!(matches!(sponge.absorb(b"abc"), Err(ShaError::InvalidState(_))));
+    assert!(matches!(sponge.pad(0x06), Err(ShaError::InvalidState(_))));
+    assert!(sponge.squeeze(&mut [0; 8]).is_ok());
+}
@@ -0,0 +1,81 @@
+use sha_algorithm::hasher::{self, Hasher};
+use sha_algorithm::sha3::ShakeHasher;
+use sha_algorithm::{Digest, ShaAlgorithm, ShaError};
+
+fn shake(msg: &[u8], algorithm: ShaAlgorithm) -> String {
+    hasher::hash_bytes(msg, &algorithm).unwrap().digest().to_string()
+}
+
+// NIST SHAKE examples: empty message and 1600 bits of 0xa3 with 4096-bit outputs
+#[test]
+fn nist_examples() {
+    assert_eq!(shake(b"", ShaAlgorithm::SHAKE128(256)),
+        "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26");
+    assert_eq!(shake(b"", ShaAlgorithm::SHAKE256(512)),
+        "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762fd75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be");
+    assert_eq!(shake(&[0xa3; 200], ShaAlgorithm::SHAKE128(4096)),
+        "131ab8d2b594946b9c81333f9bb6e0ce75c3b93104fa3469d3917457385da037cf232ef7164a6d1eb448c8908186ad852d3f85a5cf28da1ab6fe3438171978467f1c05d58c7ef38c284c41f6c2221a76f12ab1c04082660250802294fb87180213fdef5b0ecb7df50ca1f8555be14d32e10f6edcde892c09424b29f597afc270c904556bfcb47a7d40778d390923642b3cbd0579e60908d5a000c1d08b98ef933f806445bf87f8b009ba9e94f7266122ed7ac24e5e266c42a82fa1bbefb7b8db0066e16a85e0493f07df4809aec084a593748ac3dde5a6d7aae1e8b6e5352b2d71efbb47d4caeed5e6d633805d2d323e6fd81b4684b93a2677d45e7421c2c6aea259b855a698fd7d13477a1fe53e5a4a6197dbec5ce95f505b520bcd9570c4a8265a7e01f89c0c002c59bfec6cd4a5c109258953ee5ee70cd577ee217af21fa70178f0946c9bf6ca8751793479f6b537737e40b6ed28511d8a2d7e73eb75f8daac912ff906e0ab955b083bac45a8e5e9b744c8506f37e9b4e749a184b30f43eb188d855f1b70d71ff3e50c537ac1b0f8974f0fe1a6ad295ba42f6aec74d123a7abedde6e2c0711cab36be5acb1a5a11a4b1db08ba6982efccd716929a7741cfc63aa4435e0b69a9063e880795c3dc5ef3272e11c497a91acf699fefee206227a44c9fb359fd56ac0a9a75a743cff6862f17d7259ab075216c0699511643b6439");
+    assert_eq!(shake(&[0xa3; 200], ShaAlgorithm::SHAKE256(4096)),
+        "cd8a920ed141aa0407a22d59288652e9d9f1a7ee0c1e7c1ca699424da84a904d2d700caae7396ece96604440577da4f3aa22aeb8857f961c4cd8e06f0ae6610b1048a7f64e1074cd629e85ad7566048efc4fb500b486a3309a8f26724c0ed628001a1099422468de726f1061d99eb9e93604d5aa7467d4b1bd6484582a384317d7f47d750b8f5499512bb85a226c4243556e696f6bd072c5aa2d9b69730244b56853d16970ad817e213e470618178001c9fb56c54fefa5fee67d2da524bb3b0b61ef0e9114a92cdbb6cccb98615cfe76e3510dd88d1cc28ff99287512f24bfafa1a76877b6f37198e3a641c68a7c42d45fa7acc10dae5f3cefb7b735f12d4e589f7a456e78c0f5e4c4471fffa5e4fa0514ae974d8c2648513b5db494cea847156d277ad0e141c24c7839064cd08851bc2e7ca109fd4e251c35bb0a04fb05b364ff8c4d8b59bc303e25328c09a882e952518e1a8ae0ff265d61c465896973d7490499dc639fb8502b39456791b1b6ec5bcc5d9ac36a6df622a070d43fed781f5f149f7b62675e7d1a4d6dec48c1c7164586eae06a51208c0b791244d307726505c3ad4b26b6822377257aa152037560a739714a3ca79bd605547c9b78dd1f596f2d4f1791bc689a0e9b799a37339c04275733740143ef5d2b58b96a363d4e08076a1a9d7846436e4dca5728b6f760eef0ca92bf0be5615e96959d767197a0beeb");
+}
+
+#[test]
+fn default_lengths() {
+    assert_eq!("shake128".parse::<ShaAlgorithm>().unwrap(), ShaAlgorithm::SHAKE128(128));
+    assert_eq!("SHAKE-256/1024".parse::<ShaAlgorithm>().unwrap(), ShaAlgorithm::SHAKE256(1024));
+    let mut hasher = ShakeHasher::shake128();
+    hasher.update(b"abc").unwrap();
+    assert_eq!(Digest::from(hasher.finalize().unwrap()).to_string(), "5881092dd818bf5cf8a3ddb793fbcba7");
+    let mut hasher = ShakeHasher::shake256();
+    hasher.update(b"abc").unwrap();
+    assert_eq!(Digest::from(hasher.finalize().unwrap()).to_string(),
+        "483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739");
+}
+
+#[test]
+fn reader_matches_single_squeeze() {
+    let expected = Digest::from(hasher::hash_bytes(&[0xa3; 200], &ShaAlgorithm::SHAKE256(4096)).unwrap());
+    let mut hasher = ShakeHasher::shake256();
+    for chunk in [0xa3u8; 200].chunks(7) {
+        hasher.update(chunk).unwrap();
+    }
+    let mut reader = hasher.finalize_xof().unwrap();
+    let mut out = vec![0u8; 512];
+    // Read sizes that straddle the 136-byte rate boundary
+    let mut offset = 0;
+    for size in [1, 135, 2, 136, 200, 38] {
+        reader.read(&mut out[offset..offset + size]).unwrap();
+        offset += size;
+    }
+    assert_eq!(Digest::new(out), expected);
+}
+
+#[test]
+fn io_read() {
+    use std::io::Read;
+    let mut hasher = ShakeHasher::shake128();
+    hasher.update(b"").unwrap();
+    let mut out = [0u8; 32];
+    hasher.finalize_xof().unwrap().read_exact(&mut out).unwrap();
+    assert_eq!(Digest::new(out.to_vec()).to_string(),
+        "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26");
+
+    // The output is endless, so reading to the end needs a bound
+    let expected = hasher::hash_bytes(b"abc", &ShaAlgorithm::SHAKE256(8 * 300)).unwrap().get_values();
+    let mut hasher = ShakeHasher::shake256();
+    hasher.update(b"abc").unwrap();
+    let mut out = Vec::new();
+    assert_eq!(hasher.finalize_xof().unwrap().take(300).read_to_end(&mut out).unwrap(), 300);
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn invalid_lengths() {
+    assert!(matches!(ShakeHasher::new(&ShaAlgorithm::SHAKE128(0)), Err(ShaError::InvalidShakeLength(0))));
+    assert!(matches!(ShakeHasher::new(&ShaAlgorithm::SHAKE256(12)), Err(ShaError::InvalidShakeLength(12))));
+    assert!(ShakeHasher::new(&ShaAlgorithm::SHA3_256).is_err());
+    assert!(Hasher::new(&ShaAlgorithm::SHAKE128(7)).is_err());
+    assert!(matches!("shake128/0".parse::<ShaAlgorithm>(), Err(ShaError::InvalidShakeLength(0))));
+    assert!(matches!("shake256/12".parse::<ShaAlgorithm>(), Err(ShaError::InvalidShakeLength(12))));
+    assert!(matches!("shake256/512".parse::<ShaAlgorithm>(), Ok(ShaAlgorithm::SHAKE256(512))));
+}
@@ -0,0 +1,353 @@
+use sha_algorithm::sha3::ShakeHasher;
+use sha_algorithm::sp800_185::{self, CShake, Kmac, ParallelHash, TupleHash};
+use sha_algorithm::{Digest, ShaError, XofReader};
+
+fn hex(bytes: &[u8]) -> String {
+    Digest::new(bytes.to_vec()).to_string()
+}
+
+// Message 00 01 .. c7 of the longer NIST samples
+fn bytes_200() -> Vec<u8> {
+    (0..200).collect()
+}
+
+// Key 40 41 .. 5f of the KMAC samples
+fn kmac_key() -> Vec<u8> {
+    (0x40..0x60).collect()
+}
+
+fn read(mut reader: XofReader, length: usize) -> Vec<u8> {
+    let mut output = vec![0u8; length];
+    reader.read(&mut output).unwrap();
+    output
+}
+
+#[test]
+fn encodings() {
+    assert_eq!(sp800_185::left_encode(0), [1, 0]);
+    assert_eq!(sp800_185::left_encode(256), [2, 1, 0]);
+    assert_eq!(sp800_185::right_encode(0), [0, 1]);
+    assert_eq!(sp800_185::right_encode(256), [1, 0, 2]);
+    assert_eq!(sp800_185::encode_string(b"KMAC"), [1, 32, b'K', b'M', b'A', b'C']);
+    assert_eq!(sp800_185::bytepad(&[0xaa], 4), [1, 4, 0xaa, 0]);
+}
+
+// NIST SP 800-185 cSHAKE samples
+#[test]
+fn cshake_samples() {
+    let mut cshake = CShake::cshake128(b"", b"Email Signature").unwrap();
+    cshake.update(&[0, 1, 2, 3]).unwrap();
+    assert_eq!(hex(&cshake.finalize(32).unwrap()),
+        "c1c36925b6409a04f1b504fcbca9d82b4017277cb5ed2b2065fc1d3814d5aaf5");
+    let mut cshake = CShake::cshake128(b"", b"Email Signature").unwrap();
+    cshake.update(&bytes_200()).unwrap();
+    assert_eq!(hex(&cshake.finalize(32).unwrap()),
+        "c5221d50e4f822d96a2e8881a961420f294b7b24fe3d2094baed2c6524cc166b");
+    let mut cshake = CShake::cshake256(b"", b"Email Signature").unwrap();
+    cshake.update(&[0, 1, 2, 3]).unwrap();
+    assert_eq!(hex(&cshake.finalize(64).unwrap()),
+        "d008828e2b80ac9d2218ffee1d070c48b8e4c87bff32c9699d5b6896eee0edd164020e2be0560858d9c00c037e34a96937c561a74c412bb4c746469527281c8c");
+    let mut cshake = CShake::cshake256(b"", b"Email Signature").unwrap();
+    cshake.update(&bytes_200()).unwrap();
+    assert_eq!(hex(&cshake.finalize(64).unwrap()),
+        "07dc27b11e51fbac75bc7b3c1d983e8b4b85fb1defaf218912ac86430273091727f42b17ed1df63e8ec118f04b23633c1dfb1574c8fb55cb45da8e25afb092bb");
+}
+
+#[test]
+fn cshake_without_strings_is_shake() {
+    let mut cshake = CShake::cshake128(b"", b"").unwrap();
+    cshake.update(b"abc").unwrap();
+    assert_eq!(hex(&cshake.finalize(16).unwrap()), "5881092dd818bf5cf8a3ddb793fbcba7");
+}
+
+// NIST SP 800-185 KMAC samples
+#[test]
+fn kmac_samples() {
+    let mut mac = Kmac::kmac128(&kmac_key(), b"").unwrap();
+    mac.update(&[0, 1, 2, 3]).unwrap();
+    assert_eq!(hex(&mac.finalize(32).unwrap()),
+        "e5780b0d3ea6f7d3a429c5706aa43a00fadbd7d49628839e3187243f456ee14e");
+    let mut mac = Kmac::kmac128(&kmac_key(), b"My Tagged Application").unwrap();
+    mac.update(&[0, 1, 2, 3]).unwrap();
+    assert_eq!(hex(&mac.finalize(32).unwrap()),
+        "3b1fba963cd8b0b59e8c1a6d71888b7143651af8ba0a7070c0979e2811324aa5");
+    let mut mac = Kmac::kmac128(&kmac_key(), b"My Tagged Application").unwrap();
+    mac.update(&bytes_200()).unwrap();
+    assert_eq!(hex(&mac.finalize(32).unwrap()),
+        "1f5b4e6cca02209e0dcb5ca635b89a15e271ecc760071dfd805faa38f9729230");
+    let mut mac = Kmac::kmac256(&kmac_key(), b"My Tagged Application").unwrap();
+    mac.update(&[0, 1, 2, 3]).unwrap();
+    assert_eq!(hex(&mac.finalize(64).unwrap()),
+        "20c570c31346f703c9ac36c61c03cb64c3970d0cfc787e9b79599d273a68d2f7f69d4cc3de9d104a351689f27cf6f5951f0103f33f4f24871024d9c27773a8dd");
+    let mut mac = Kmac::kmac256(&kmac_key(), b"").unwrap();
+    mac.update(&bytes_200()).unwrap();
+    assert_eq!(hex(&mac.finalize(64).unwrap()),
+        "75358cf39e41494e949707927cee0af20a3ff553904c86b08f21cc414bcfd691589d27cf5e15369cbbff8b9a4c2eb17800855d0235ff635da82533ec6b759b69");
+    let mut mac = Kmac::kmac256(&kmac_key(), b"My Tagged Application").unwrap();
+    mac.update(&bytes_200()).unwrap();
+    assert_eq!(hex(&mac.finalize(64).unwrap()),
+        "b58618f71f92e1d56c1b8c55ddd7cd188b97b4ca4d99831eb2699a837da2e4d970fbacfde50033aea585f1a2708510c32d07880801bd182898fe476876fc8965");
+}
+
+// NIST SP 800-185 KMACXOF samples
+#[test]
+fn kmac_xof_samples() {
+    let mut mac = Kmac::kmac128(&kmac_key(), b"").unwrap();
+    mac.update(&[0, 1, 2, 3]).unwrap();
+    assert_eq!(hex(&read(mac.finalize_xof().unwrap(), 32)),
+        "cd83740bbd92ccc8cf032b1481a0f4460e7ca9dd12b08a0c4031178bacd6ec35");
+    let mut mac = Kmac::kmac128(&kmac_key(), b"My Tagged Application").unwrap();
+    mac.update(&[0, 1, 2, 3]).unwrap();
+    assert_eq!(hex(&read(mac.finalize_xof().unwrap(), 32)),
+        "31a44527b4ed9f5c6101d11de6d26f0620aa5c341def41299657fe9df1a3b16c");
+    let mut mac = Kmac::kmac128(&kmac_key(), b"My Tagged Application").unwrap();
+    mac.update(&bytes_200()).unwrap();
+    assert_eq!(hex(&read(mac.finalize_xof().unwrap(), 32)),
+        "47026c7cd793084aa0283c253ef658490c0db61438b8326fe9bddf281b83ae0f");
+    let mut mac = Kmac::kmac256(&kmac_key(), b"My Tagged Application").unwrap();
+    mac.update(&[0, 1, 2, 3]).unwrap();
+    assert_eq!(hex(&read(mac.finalize_xof().unwrap(), 64)),
+        "1755133f1534752aad0748f2c706fb5c784512cab835cd15676b16c0c6647fa96faa7af634a0bf8ff6df39374fa00fad9a39e322a7c92065a64eb1fb0801eb2b");
+    let mut mac = Kmac::kmac256(&kmac_key(), b"").unwrap();
+    mac.update(&bytes_200()).unwrap();
+    assert_eq!(hex(&read(mac.finalize_xof().unwrap(), 64)),
+        "ff7b171f1e8a2b24683eed37830ee797538ba8dc563f6da1e667391a75edc02ca633079f81ce12a25f45615ec89972031d18337331d24ceb8f8ca8e6a19fd98b");
+    let mut mac = Kmac::kmac256(&kmac_key(), b"My Tagged Application").unwrap();
+    mac.update(&bytes_200()).unwrap();
+    assert_eq!(hex(&read(mac.finalize_xof().unwrap(), 64)),
+        "d5be731c954ed7732846bb59dbe3a8e30f83e77a4bff4459f2f1c2b4ecebb8ce67ba01c62e8ab8578d2d499bd1bb276768781190020a306a97de281dcc30305d");
+}
+
+#[test]
+fn kmac_verify() {
+    let mut mac = Kmac::kmac128(&kmac_key(), b"").unwrap();
+    mac.update(&[0, 1, 2, 3]).unwrap();
+    let tag = mac.clone().finalize(32).unwrap();
+    assert!(mac.clone().verify(&tag, 32).is_ok());
+    // The length is part of the MAC, so a truncated tag doesn't verify
+    assert!(matches!(mac.clone().verify(&tag[..16], 32), Err(ShaError::InvalidMac)));
+    let mut wrong = tag.clone();
+    wrong[31] ^= 1;
+    assert!(matches!(mac.clone().verify(&wrong, 32), Err(ShaError::InvalidMac)));
+    // Even a correct MAC of the tag's own length doesn't verify when it isn't the expected one
+    let short = mac.clone().finalize(16).unwrap();
+    assert!(matches!(mac.clone().verify(&short, 32), Err(ShaError::InvalidMac)));
+}
+
+#[test]
+fn kmac_verify_rejects_short_tags() {
+    let mut mac = Kmac::kmac256(&kmac_key(), b"").unwrap();
+    mac.update(b"message").unwrap();
+    assert!(matches!(mac.clone().verify(&[], 32), Err(ShaError::InvalidMac)));
+    for guess in 0..=255u8 {
+        assert!(matches!(mac.clone().verify(&[guess], 32), Err(ShaError::InvalidMac)), "{}", guess);
+    }
+    let one_byte = mac.clone().finalize(1).unwrap();
+    assert!(matches!(mac.clone().verify(&one_byte, 1), Err(ShaError::InvalidOutputLength(1))));
+    assert!(matches!(mac.clone().verify(&[], 0), Err(ShaError::InvalidOutputLength(0))));
+    let minimal = mac.clone().finalize(4).unwrap();
+    assert!(mac.verify(&minimal, 4).is_ok());
+}
+
+// NIST SP 800-185 TupleHash samples
+#[test]
+fn tuplehash_samples() {
+    let mut tuple = TupleHash::tuplehash128(b"").unwrap();
+    tuple.push(&[0x00, 0x01, 0x02]).unwrap();
+    tuple.push(&[0x10, 0x11, 0x12, 0x13, 0x14, 0x15]).unwrap();
+    assert_eq!(hex(&tuple.finalize(32).unwrap()),
+        "c5d8786c1afb9b82111ab34b65b2c0048fa64e6d48e263264ce1707d3ffc8ed1");
+    let mut tuple = TupleHash::tuplehash128(b"My Tuple App").unwrap();
+    tuple.push(&[0x00, 0x01, 0x02]).unwrap();
+    tuple.push(&[0x10, 0x11, 0x12, 0x13, 0x14, 0x15]).unwrap();
+    assert_eq!(hex(&tuple.finalize(32).unwrap()),
+        "75cdb20ff4db1154e841d758e24160c54bae86eb8c13e7f5f40eb35588e96dfb");
+    let mut tuple = TupleHash::tuplehash128(b"My Tuple App").unwrap();
+    tuple.push(&[0x00, 0x01, 0x02]).unwrap();
+    tuple.push(&[0x10, 0x11, 0x12, 0x13, 0x14, 0x15]).unwrap();
+    tuple.push(&[0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28]).unwrap();
+    assert_eq!(hex(&tuple.finalize(32).unwrap()),
+        "e60f202c89a2631eda8d4c588ca5fd07f39e5151998deccf973adb3804bb6e84");
+    let mut tuple = TupleHash::tuplehash256(b"").unwrap();
+    tuple.push(&[0x00, 0x01, 0x02]).unwrap();
+    tuple.push(&[0x10, 0x11, 0x12, 0x13, 0x14, 0x15]).unwrap();
+    assert_eq!(hex(&tuple.finalize(64).unwrap()),
+        "cfb7058caca5e668f81a12a20a2195ce97a925f1dba3e7449a56f82201ec607311ac2696b1ab5ea2352df1423bde7bd4bb78c9aed1a853c78672f9eb23bbe194");
+    let mut tuple = TupleHash::tuplehash256(b"My Tuple App").unwrap();
+    tuple.push(&[0x00, 0x01, 0x02]).unwrap();
+    tuple.push(&[0x10, 0x11, 0x12, 0x13, 0x14, 0x15]).unwrap();
+    assert_eq!(hex(&tuple.finalize(64).unwrap()),
+        "147c2191d5ed7efd98dbd96d7ab5a11692576f5fe2a5065f3e33de6bba9f3aa1c4e9a068a289c61c95aab30aee1e410b0b607de3620e24a4e3bf9852a1d4367e");
+    let mut tuple = TupleHash::tuplehash256(b"My Tuple App").unwrap();
+    tuple.push(&[0x00, 0x01, 0x02]).unwrap();
+    tuple.push(&[0x10, 0x11, 0x12, 0x13, 0x14, 0x15]).unwrap();
+    tuple.push(&[0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28]).unwrap();
+    assert_eq!(hex(&tuple.finalize(64).unwrap()),
+        "45000be63f9b6bfd89f54717670f69a9bc763591a4f05c50d68891a744bcc6e7d6d5b5e82c018da999ed35b0bb49c9678e526abd8e85c13ed254021db9e790ce");
+}
+
+// NIST SP 800-185 TupleHashXOF samples
+#[test]
+fn tuplehash_xof_samples() {
+    let mut tuple = TupleHash::tuplehash128(b"").unwrap();
+    tuple.push(&[0x00, 0x01, 0x02]).unwrap();
+    tuple.push(&[0x10, 0x11, 0x12, 0x13, 0x14, 0x15]).unwrap();
+    assert_eq!(hex(&read(tuple.finalize_xof().unwrap(), 32)),
+        "2f103cd7c32320353495c68de1a8129245c6325f6f2a3d608d92179c96e68488");
+    let mut tuple = TupleHash::tuplehash128(b"My Tuple App").unwrap();
+    tuple.push(&[0x00, 0x01, 0x02]).unwrap();
+    tuple.push(&[0x10, 0x11, 0x12, 0x13, 0x14, 0x15]).unwrap();
+    assert_eq!(hex(&read(tuple.finalize_xof().unwrap(), 32)),
+        "3fc8ad69453128292859a18b6c67d7ad85f01b32815e22ce839c49ec374e9b9a");
+    let mut tuple = TupleHash::tuplehash128(b"My Tuple App").unwrap();
+    tuple.push(&[0x00, 0x01, 0x02]).unwrap();
+    tuple.push(&[0x10, 0x11, 0x12, 0x13, 0x14, 0x15]).unwrap();
+    tuple.push(&[0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28]).unwrap();
+    assert_eq!(hex(&read(tuple.finalize_xof().unwrap(), 32)),
+        "900fe16cad098d28e74d632ed852f99daab7f7df4d99e775657885b4bf76d6f8");
+    let mut tuple = TupleHash::tuplehash256(b"").unwrap();
+    tuple.push(&[0x00, 0x01, 0x02]).unwrap();
+    tuple.push(&[0x10, 0x11, 0x12, 0x13, 0x14, 0x15]).unwrap();
+    assert_eq!(hex(&read(tuple.finalize_xof().unwrap(), 64)),
+        "03ded4610ed6450a1e3f8bc44951d14fbc384ab0efe57b000df6b6df5aae7cd568e77377daf13f37ec75cf5fc598b6841d51dd207c991cd45d210ba60ac52eb9");
+    let mut tuple = TupleHash::tuplehash256(b"My Tuple App").unwrap();
+    tuple.push(&[0x00, 0x01, 0x02]).unwrap();
+    tuple.push(&[0x10, 0x11, 0x12, 0x13, 0x14, 0x15]).unwrap();
+    assert_eq!(hex(&read(tuple.finalize_xof().unwrap(), 64)),
+        "6483cb3c9952eb20e830af4785851fc597ee3bf93bb7602c0ef6a65d741aeca7e63c3b128981aa05c6d27438c79d2754bb1b7191f125d6620fca12ce658b2442");
+    let mut tuple = TupleHash::tuplehash256(b"My Tuple App").unwrap();
+    tuple.push(&[0x00, 0x01, 0x02]).unwrap();
+    tuple.push(&[0x10, 0x11, 0x12, 0x13, 0x14, 0x15]).unwrap();
+    tuple.push(&[0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28]).unwrap();
+    assert_eq!(hex(&read(tuple.finalize_xof().unwrap(), 64)),
+        "0c59b11464f2336c34663ed51b2b950bec743610856f36c28d1d088d8a2446284dd09830a6a178dc752376199fae935d86cfdee5913d4922dfd369b66a53c897");
+}
+
+#[test]
+fn tuplehash_is_unambiguous() {
+    let mut first = TupleHash::tuplehash128(b"").unwrap();
+    first.push(b"ab").unwrap();
+    first.push(b"c").unwrap();
+    let mut second = TupleHash::tuplehash128(b"").unwrap();
+    second.push(b"a").unwrap();
+    second.push(b"bc").unwrap();
+    assert_ne!(first.finalize(32).unwrap(), second.finalize(32).unwrap());
+}
+
+// Messages 00..07 10..17 20..27 (..57) of the ParallelHash samples
+fn parallel_24() -> Vec<u8> {
+    (0..3).flat_map(|i| 16 * i..16 * i + 8).collect()
+}
+
+fn parallel_48() -> Vec<u8> {
+    (0..6).flat_map(|i| 16 * i..16 * i + 8).collect()
+}
+
+// NIST SP 800-185 ParallelHash samples
+#[test]
+fn parallelhash_samples() {
+    let mut hash = ParallelHash::parallelhash128(8, b"").unwrap();
+    hash.update(&parallel_24()).unwrap();
+    assert_eq!(hex(&hash.finalize(32).unwrap()),
+        "ba8dc1d1d979331d3f813603c67f72609ab5e44b94a0b8f9af46514454a2b4f5");
+    let mut hash = ParallelHash::parallelhash128(8, b"Parallel Data").unwrap();
+    hash.update(&parallel_24()).unwrap();
+    assert_eq!(hex(&hash.finalize(32).unwrap()),
+        "fc484dcb3f84dceedc353438151bee58157d6efed0445a81f165e495795b7206");
+    let mut hash = ParallelHash::parallelhash128(12, b"Parallel Data").unwrap();
+    hash.update(&parallel_48()).unwrap();
+    assert_eq!(hex(&hash.finalize(32).unwrap()),
+        "7a5fbf125bdd5bb76f3a578e2a4e097bb9718bbada686fb647d6f34da16ffa33");
+    let mut hash = ParallelHash::parallelhash256(8, b"").unwrap();
+    hash.update(&parallel_24()).unwrap();
+    assert_eq!(hex(&hash.finalize(64).unwrap()),
+        "bc1ef124da34495e948ead207dd9842235da432d2bbc54b4c110e64c451105531b7f2a3e0ce055c02805e7c2de1fb746af97a1dd01f43b824e31b87612410429");
+    let mut hash = ParallelHash::parallelhash256(8, b"Parallel Data").unwrap();
+    hash.update(&parallel_24()).unwrap();
+    assert_eq!(hex(&hash.finalize(64).unwrap()),
+        "cdf15289b54f6212b4bc270528b49526006dd9b54e2b6add1ef6900dda3963bb33a72491f236969ca8afaea29c682d47a393c065b38e29fae651a2091c833110");
+    let mut hash = ParallelHash::parallelhash256(12, b"Parallel Data").unwrap();
+    hash.update(&parallel_48()).unwrap();
+    assert_eq!(hex(&hash.finalize(64).unwrap()),
+        "feea4e5c7b68ea5bbfd8b0310ebd01b62bc0bf06a0237751deaab5544251401fb3621c26e9c9a23d5f783d61c161f9fec2d837fc7e0b0a5b1ba6558e8531a68b");
+}
+
+// NIST SP 800-185 ParallelHashXOF samples
+#[test]
+fn parallelhash_xof_samples() {
+    let mut hash = ParallelHash::parallelhash128(8, b"").unwrap();
+    hash.update(&parallel_24()).unwrap();
+    assert_eq!(hex(&read(hash.finalize_xof().unwrap(), 32)),
+        "fe47d661e49ffe5b7d999922c062356750caf552985b8e8ce6667f2727c3c8d3");
+    let mut hash = ParallelHash::parallelhash128(8, b"Parallel Data").unwrap();
+    hash.update(&parallel_24()).unwrap();
+    assert_eq!(hex(&read(hash.finalize_xof().unwrap(), 32)),
+        "ea2a793140820f7a128b8eb70a9439f93257c6e6e79b4a540d291d6dae7098d7");
+    let mut hash = ParallelHash::parallelhash128(12, b"Parallel Data").unwrap();
+    hash.update(&parallel_48()).unwrap();
+    assert_eq!(hex(&read(hash.finalize_xof().unwrap(), 32)),
+        "57cc03634a945e3d98c0fc119a21ccb39a93940dc423af69dc4f69bfdff5aa59");
+    let mut hash = ParallelHash::parallelhash256(8, b"").unwrap();
+    hash.update(&parallel_24()).unwrap();
+    assert_eq!(hex(&read(hash.finalize_xof().unwrap(), 64)),
+        "c10a052722614684144d28474850b410757e3cba87651ba167a5cbddff7f466675fbf84bcae7378ac444be681d729499afca667fb879348bfdda427863c82f1c");
+    let mut hash = ParallelHash::parallelhash256(8, b"Parallel Data").unwrap();
+    hash.update(&parallel_24()).unwrap();
+    assert_eq!(hex(&read(hash.finalize_xof().unwrap(), 64)),
+        "538e105f1a22f44ed2f5cc1674fbd40be803d9c99bf5f8d90a2c8193f3fe6ea768e5c1a20987e2c9c65febed03887a51d35624ed12377594b5585541dc377efc");
+    let mut hash = ParallelHash::parallelhash256(12, b"Parallel Data").unwrap();
+    hash.update(&parallel_48()).unwrap();
+    assert_eq!(hex(&read(hash.finalize_xof().unwrap(), 64)),
+        "ec6cb77a08b968d775602782e47816fc9d4d038a8a97420e9876cb5508e7abcba51315ec9b927719364a2c4a9d05e2085ca4d0bf12cf8200785db2ea694fa7ca");
+}
+
+#[test]
+fn parallelhash_streaming() {
+    let data = bytes_200();
+    let expected = {
+        let mut hash = ParallelHash::parallelhash256(16, b"").unwrap();
+        hash.update(&data).unwrap();
+        hash.finalize(64).unwrap()
+    };
+    let mut hash = ParallelHash::parallelhash256(16, b"").unwrap();
+    for chunk in data.chunks(7) {
+        hash.update(chunk).unwrap();
+    }
+    assert_eq!(hash.finalize(64).unwrap(), expected);
+    assert_eq!(hex(&{
+        let mut hash = ParallelHash::parallelhash128(16, b"").unwrap();
+        hash.update(&data).unwrap();
+        hash.finalize(32).unwrap()
+    }), "a1d8dc284b5c1dff40fab25ab41b94803be3d93e0d2f10461d5cbde14ef7514b");
+    assert!(matches!(ParallelHash::parallelhash128(0, b""), Err(ShaError::InvalidBlockSize(0))));
+    assert!(matches!(ParallelHash::parallelhash256(0, b""), Err(ShaError::InvalidBlockSize(0))));
+}
+
+// ParallelHash computed from its definition in SP 800-185 §6.3
+fn parallelhash128_reference(msg: &[u8], block_size: usize, customization: &[u8], length: usize) -> Vec<u8> {
+    let mut cshake = CShake::cshake128(b"ParallelHash", customization).unwrap();
+    cshake.update(&sp800_185::left_encode(block_size as u64)).unwrap();
+    let blocks = msg.chunks(block_size).collect::<Vec<_>>();
+    for block in &blocks {
+        let mut shake = ShakeHasher::shake128();
+        shake.update(block).unwrap();
+        cshake.update(&read(shake.finalize_xof().unwrap(), 32)).unwrap();
+    }
+    cshake.update(&sp800_185::right_encode(blocks.len() as u64)).unwrap();
+    cshake.update(&sp800_185::right_encode(length as u64 * 8)).unwrap();
+    cshake.finalize(length).unwrap()
+}
+
+#[test]
+fn parallelhash_huge_block_size() {
+    // Nothing of the block size is allocated, so any size is usable
+    let msg = bytes_200();
+    for block_size in [usize::MAX / 2, 1 << 30] {
+        let mut hash = ParallelHash::parallelhash128(block_size, b"Parallel Data").unwrap();
+        hash.update(&msg).unwrap();
+        assert_eq!(hash.finalize(32).unwrap(), parallelhash128_reference(&msg, block_size, b"Parallel Data", 32));
+    }
+    let mut hash = ParallelHash::parallelhash128(16, b"").unwrap();
+    hash.update(&msg).unwrap();
+    assert_eq!(hash.finalize(32).unwrap(), parallelhash128_reference(&msg, 16, b"", 32));
+}
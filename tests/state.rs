@@ -0,0 +1,122 @@
+use sha_algorithm::hasher::{self, Hasher};
+use sha_algorithm::{sha2, Sha1Hasher, Sha2Hasher, ShaAlgorithm, ShaError};
+
+const ALGORITHMS: [ShaAlgorithm; 7] = [
+    ShaAlgorithm::SHA1,
+    ShaAlgorithm::SHA224,
+    ShaAlgorithm::SHA256,
+    ShaAlgorithm::SHA384,
+    ShaAlgorithm::SHA512,
+    ShaAlgorithm::SHA512T(224),
+    ShaAlgorithm::SHA512T(256),
+];
+
+fn message() -> Vec<u8> {
+    (0..1000u32).map(|i| (i % 251) as u8).collect()
+}
+
+// Replaces the trailing SHA-256 checksum so that only the edited field is wrong
+fn reseal(blob: &mut Vec<u8>) {
+    let body_len = blob.len() - 32;
+    let sum = sha2::hash_message_bytes(&blob[..body_len], &ShaAlgorithm::SHA256).unwrap().get_values();
+    blob.truncate(body_len);
+    blob.extend(sum);
+}
+
+fn checkpoint(algorithm: ShaAlgorithm, split: usize) -> Vec<u8> {
+    let mut hasher = Hasher::new(&algorithm).unwrap();
+    hasher.update(&message()[..split]).unwrap();
+    hasher.export_state().unwrap()
+}
+
+#[test]
+fn resume_matches_uninterrupted_run() {
+    let msg = message();
+    for algorithm in ALGORITHMS {
+        let expected = hasher::hash_bytes(&msg, &algorithm).unwrap().digest();
+        for split in [0, 1, 55, 64, 127, 128, 500, 1000] {
+            let blob = checkpoint(algorithm, split);
+            let mut resumed = Hasher::import_state(&blob).unwrap();
+            resumed.update(&msg[split..]).unwrap();
+            assert_eq!(resumed.finalize().unwrap().digest(), expected, "{} split at {}", algorithm, split);
+        }
+    }
+}
+
+#[test]
+fn resume_with_concrete_hashers() {
+    let msg = message();
+    let mut hasher = Sha1Hasher::new();
+    hasher.update(&msg[..300]).unwrap();
+    let mut resumed = Sha1Hasher::import_state(&hasher.export_state().unwrap()).unwrap();
+    resumed.update(&msg[300..]).unwrap();
+    assert_eq!(resumed.finalize().unwrap().digest(), hasher::hash_bytes(&msg, &ShaAlgorithm::SHA1).unwrap().digest());
+
+    let mut hasher = Sha2Hasher::new(&ShaAlgorithm::SHA384).unwrap();
+    hasher.update(&msg[..300]).unwrap();
+    let mut resumed = Sha2Hasher::import_state(&hasher.export_state().unwrap()).unwrap();
+    resumed.update(&msg[300..]).unwrap();
+    assert_eq!(resumed.finalize().unwrap().digest(), hasher::hash_bytes(&msg, &ShaAlgorithm::SHA384).unwrap().digest());
+}
+
+#[test]
+fn resume_after_partial_byte() {
+    let mut hasher = Hasher::new(&ShaAlgorithm::SHA256).unwrap();
+    hasher.update_bits(&[0x68], 5).unwrap();
+    let resumed = Hasher::import_state(&hasher.export_state().unwrap()).unwrap();
+    assert_eq!(resumed.finalize().unwrap().digest().to_string(),
+        "d6d3e02a31a84a8caa9718ed6c2057be09db45e7823eb5079ce7a573a3760f95");
+}
+
+#[test]
+fn mismatched_algorithm() {
+    let sha1 = checkpoint(ShaAlgorithm::SHA1, 10);
+    let sha256 = checkpoint(ShaAlgorithm::SHA256, 10);
+    assert!(matches!(Sha2Hasher::import_state(&sha1), Err(ShaError::InvalidState(_))));
+    assert!(matches!(Sha1Hasher::import_state(&sha256), Err(ShaError::InvalidState(_))));
+    assert!(matches!(Hasher::new(&ShaAlgorithm::SHA3_256).unwrap().export_state(), Err(ShaError::InvalidAlgorithm)));
+}
+
+#[test]
+fn corrupt_blobs() {
+    let blob = checkpoint(ShaAlgorithm::SHA512, 200);
+    for i in 0..blob.len() {
+        let mut corrupt = blob.clone();
+        corrupt[i] ^= 0x01;
+        assert!(Hasher::import_state(&corrupt).is_err(), "flipped byte {}", i);
+    }
+    for len in 0..blob.len() {
+        assert!(Hasher::import_state(&blob[..len]).is_err(), "truncated to {}", len);
+    }
+    let mut longer = blob.clone();
+    longer.push(0);
+    assert!(Hasher::import_state(&longer).is_err());
+    assert!(Hasher::import_state(b"not a state at all").is_err());
+}
+
+#[test]
+fn inconsistent_fields() {
+    // Layout: magic (4), version, id, t (2), total length (16), buffered count, buffer, H, checksum
+    let blob = checkpoint(ShaAlgorithm::SHA256, 100);
+
+    let mut version = blob.clone();
+    version[4] = 2;
+    reseal(&mut version);
+    assert!(matches!(Hasher::import_state(&version), Err(ShaError::InvalidState("unsupported version"))));
+
+    let mut id = blob.clone();
+    id[5] = 42;
+    reseal(&mut id);
+    assert!(matches!(Hasher::import_state(&id), Err(ShaError::InvalidState(_))));
+
+    // 101 bytes in total, but still 36 buffered
+    let mut total = blob.clone();
+    total[23] += 8;
+    reseal(&mut total);
+    assert!(matches!(Hasher::import_state(&total), Err(ShaError::InvalidState(_))));
+
+    let mut t = checkpoint(ShaAlgorithm::SHA512T(256), 100);
+    t[7] = 7;
+    reseal(&mut t);
+    assert!(Hasher::import_state(&t).is_err());
+}
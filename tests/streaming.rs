@@ -0,0 +1,97 @@
+use sha_algorithm::sha1::{self, Sha1Hasher};
+use sha_algorithm::sha2::{self, Sha2Hasher};
+use sha_algorithm::hasher::{self, CHUNK_SIZE};
+use sha_algorithm::ShaAlgorithm;
+
+const SHA2_ALGORITHMS: [ShaAlgorithm; 6] = [
+    ShaAlgorithm::SHA224,
+    ShaAlgorithm::SHA256,
+    ShaAlgorithm::SHA384,
+    ShaAlgorithm::SHA512,
+    ShaAlgorithm::SHA512T(224),
+    ShaAlgorithm::SHA512T(256),
+];
+
+// Messages whose lengths cross every padding boundary of both block sizes
+fn messages() -> Vec<String> {
+    (0..300).map(|len| (0..len).map(|i| (b'a' + (i % 26) as u8) as char).collect()).collect()
+}
+
+fn sha1_streamed(msg: &[u8], chunk: usize) -> Vec<u8> {
+    let mut hasher = Sha1Hasher::new();
+    for part in msg.chunks(chunk) {
+        hasher.update(part).unwrap();
+    }
+    hasher.finalize().unwrap().get_values()
+}
+
+fn sha2_streamed(msg: &[u8], chunk: usize, algorithm: &ShaAlgorithm) -> Vec<u8> {
+    let mut hasher = Sha2Hasher::new(algorithm).unwrap();
+    for part in msg.chunks(chunk) {
+        hasher.update(part).unwrap();
+    }
+    hasher.finalize().unwrap().get_values()
+}
+
+#[test]
+fn sha1_streaming_matches_one_shot() {
+    for msg in messages() {
+        let expected = sha1::hash_message(&msg, &ShaAlgorithm::SHA1).unwrap().get_values();
+        for chunk in [1, 3, 63, 64, 65, 127, 128, 129, 300] {
+            assert_eq!(sha1_streamed(msg.as_bytes(), chunk), expected, "len {} chunk {}", msg.len(), chunk);
+        }
+    }
+}
+
+#[test]
+fn sha2_streaming_matches_one_shot() {
+    for algorithm in SHA2_ALGORITHMS.iter() {
+        for msg in messages() {
+            let expected = sha2::hash_message(&msg, algorithm).unwrap().get_values();
+            for chunk in [1, 7, 63, 64, 65, 127, 128, 129, 300] {
+                assert_eq!(sha2_streamed(msg.as_bytes(), chunk, algorithm), expected,
+                    "{:?} len {} chunk {}", algorithm, msg.len(), chunk);
+            }
+        }
+    }
+}
+
+#[test]
+fn streaming_known_answers() {
+    let msg = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+    assert_eq!(hex(&sha1_streamed(msg, 5)), "84983e441c3bd26ebaae4aa1f95129e5e54670f1");
+    assert_eq!(hex(&sha2_streamed(msg, 5, &ShaAlgorithm::SHA256)),
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1");
+}
+
+#[test]
+fn sha2_hasher_rejects_sha1() {
+    assert!(Sha2Hasher::new(&ShaAlgorithm::SHA1).is_err());
+}
+
+#[test]
+fn reader_spanning_several_chunks() {
+    let data: Vec<u8> = (0..CHUNK_SIZE * 2 + 77).map(|i| (i % 251) as u8).collect();
+    let expected = sha2::hash_message_bytes(&data, &ShaAlgorithm::SHA512).unwrap().get_values();
+    let hash = hasher::hash_reader(&data[..], &ShaAlgorithm::SHA512).unwrap();
+    assert_eq!(hash.get_values(), expected);
+}
+
+#[test]
+fn file_keeps_trailing_whitespace() {
+    let path = std::env::temp_dir().join(format!("sha_algorithm_streaming_{}.txt", std::process::id()));
+    std::fs::write(&path, b"hello\n  \n").unwrap();
+    let hash = hasher::hash_file(&path, &ShaAlgorithm::SHA256);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(hex(&hash.unwrap().get_values()), "e86efafdd3f7cf3e5f8f20cfdb39f2d4bf31bfbd80b49c3076087372652f1e62");
+}
+
+#[test]
+fn missing_file_is_an_io_error() {
+    let hash = hasher::hash_file("/nonexistent/sha_algorithm/file", &ShaAlgorithm::SHA1);
+    assert!(matches!(hash, Err(sha_algorithm::ShaError::Io(_))));
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
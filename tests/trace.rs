@@ -0,0 +1,92 @@
+use sha_algorithm::hasher;
+use sha_algorithm::trace::trace_message;
+use sha_algorithm::{ShaAlgorithm, ShaError};
+
+// Values from the "abc" examples of FIPS 180-2, appendices A.1, B.1 and C.1
+#[test]
+fn sha1_abc() {
+    let (hash, trace) = trace_message(b"abc", &ShaAlgorithm::SHA1).unwrap();
+    assert_eq!(hash.digest().to_string(), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    assert_eq!(trace.initial_hash, [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0]);
+    assert_eq!(trace.blocks.len(), 1);
+    let block = &trace.blocks[0];
+    assert_eq!(block.schedule.len(), 80);
+    assert_eq!(block.schedule[0], 0x61626380);
+    assert_eq!(block.schedule[15], 0x00000018);
+    assert_eq!(block.rounds.len(), 80);
+    assert_eq!(block.rounds[0], [0x0116fc33, 0x67452301, 0x7bf36ae2, 0x98badcfe, 0x10325476]);
+    assert_eq!(block.rounds[79], [0x42541b35, 0x5738d5e1, 0x21834873, 0x681e6df6, 0xd8fdf6ad]);
+    assert_eq!(block.hash, [0xa9993e36, 0x4706816a, 0xba3e2571, 0x7850c26c, 0x9cd0d89d]);
+}
+
+#[test]
+fn sha256_abc() {
+    let (_, trace) = trace_message(b"abc", &ShaAlgorithm::SHA256).unwrap();
+    let block = &trace.blocks[0];
+    assert_eq!(block.schedule.len(), 64);
+    assert_eq!(block.schedule[0], 0x61626380);
+    assert_eq!(block.rounds.len(), 64);
+    assert_eq!(block.rounds[0], [0x5d6aebcd, 0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xfa2a4622, 0x510e527f, 0x9b05688c, 0x1f83d9ab]);
+    assert_eq!(block.rounds[63], [0x506e3058, 0xd39a2165, 0x04d24d6c, 0xb85e2ce9, 0x5ef50f24, 0xfb121210, 0x948d25b6, 0x961f4894]);
+    assert_eq!(block.hash[0], 0xba7816bf);
+    assert_eq!(block.hash[7], 0xf20015ad);
+}
+
+#[test]
+fn sha512_abc() {
+    let (_, trace) = trace_message(b"abc", &ShaAlgorithm::SHA512).unwrap();
+    let block = &trace.blocks[0];
+    assert_eq!(block.schedule.len(), 80);
+    assert_eq!(block.schedule[0], 0x6162638000000000);
+    assert_eq!(block.rounds[0][0], 0xf6afceb8bcfcddf5);
+    assert_eq!(block.rounds[79][0], 0x73a54f399fa4b1b2);
+    assert_eq!(block.hash[0], 0xddaf35a193617aba);
+    assert_eq!(trace.word_bits(), 64);
+}
+
+#[test]
+fn chained_blocks() {
+    let msg = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+    for algorithm in [ShaAlgorithm::SHA1, ShaAlgorithm::SHA224, ShaAlgorithm::SHA384, ShaAlgorithm::SHA512T(256)] {
+        let (hash, trace) = trace_message(msg, &algorithm).unwrap();
+        assert_eq!(hash.digest(), hasher::hash_bytes(msg, &algorithm).unwrap().digest(), "{}", algorithm);
+        let blocks = if algorithm == ShaAlgorithm::SHA1 || algorithm == ShaAlgorithm::SHA224 { 2 } else { 1 };
+        assert_eq!(trace.blocks.len(), blocks, "{}", algorithm);
+        // Each block starts from the hash value the previous one left
+        let mut previous = &trace.initial_hash;
+        for block in trace.blocks.iter() {
+            let last = block.rounds.last().unwrap();
+            let mask = if trace.word_bits() == 32 { 0xffffffff } else { u64::MAX };
+            for i in 0..previous.len() {
+                assert_eq!(block.hash[i], previous[i].wrapping_add(last[i]) & mask);
+            }
+            previous = &block.hash;
+        }
+    }
+}
+
+#[test]
+fn exports() {
+    let (_, trace) = trace_message(b"abc", &ShaAlgorithm::SHA256).unwrap();
+
+    let json = trace.to_json();
+    assert!(json.starts_with("{\"algorithm\":\"SHA-256\",\"word_bits\":32,\"initial_hash\":[\"6a09e667\","));
+    assert!(json.contains("\"rounds\":[[\"5d6aebcd\",\"6a09e667\","));
+    assert!(json.ends_with("\"f20015ad\"]}]}"));
+
+    let csv = trace.to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 1 + 64 + 1);
+    assert_eq!(lines[0], "block,t,W,a,b,c,d,e,f,g,h");
+    assert_eq!(lines[1], "1,0,61626380,5d6aebcd,6a09e667,bb67ae85,3c6ef372,fa2a4622,510e527f,9b05688c,1f83d9ab");
+    assert_eq!(lines[65], "1,H,,ba7816bf,8f01cfea,414140de,5dae2223,b00361a3,96177a9c,b410ff61,f20015ad");
+
+    let table = trace.to_table();
+    assert!(table.contains("t= 0: 5D6AEBCD 6A09E667 BB67AE85 3C6EF372 FA2A4622 510E527F 9B05688C 1F83D9AB\n"));
+    assert!(table.contains("   H[0] = 6A09E667 + 506E3058 = BA7816BF\n"));
+}
+
+#[test]
+fn sha3_is_rejected() {
+    assert!(matches!(trace_message(b"abc", &ShaAlgorithm::SHA3_256), Err(ShaError::InvalidAlgorithm)));
+}
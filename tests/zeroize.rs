@@ -0,0 +1,119 @@
+use std::mem::{size_of, MaybeUninit};
+use std::ptr;
+
+use sha_algorithm::sha2::{Sha2Engine, Sha512};
+use sha_algorithm::sha3::Sponge;
+use sha_algorithm::trace;
+use sha_algorithm::{Hmac, Kmac, Sha1Hasher, Sha2Hasher, Sha3Hasher, ShaAlgorithm};
+
+// Recognisable bytes that fit in a partial block of every algorithm
+const SECRET: &[u8; 40] = b"correct horse battery staple, do not log";
+
+// Bytes of the memory `value` lives in, just before and just after it is
+// dropped in place. The storage outlives the drop, so it can still be read.
+fn storage_around_drop<T>(value: T) -> (Vec<u8>, Vec<u8>) {
+    let mut slot = MaybeUninit::new(value);
+    let read = |slot: &MaybeUninit<T>| -> Vec<u8> {
+        let bytes = slot.as_ptr() as *const u8;
+        // SAFETY: the slot is `size_of::<T>()` bytes long and stays allocated;
+        // volatile reads keep the compiler from assuming what a dropped value holds
+        (0..size_of::<T>()).map(|i| unsafe { ptr::read_volatile(bytes.add(i)) }).collect()
+    };
+    let before = read(&slot);
+    // SAFETY: the slot holds an initialized value, which is never used again
+    unsafe { ptr::drop_in_place(slot.as_mut_ptr()) };
+    let after = read(&slot);
+    (before, after)
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+// Checks that `secret` is in the value while alive and gone once it is dropped
+fn assert_wiped<T>(value: T, secret: &[u8]) {
+    let (before, after) = storage_around_drop(value);
+    assert!(contains(&before, secret), "the secret should be visible before the drop");
+    assert!(!contains(&after, secret), "the secret survived the drop");
+}
+
+// Chaining value after the first block of `msg`, as the words lie in memory
+fn first_chaining_value(msg: &[u8], algorithm: ShaAlgorithm) -> Vec<u8> {
+    let (_, trace) = trace::trace_message(msg, &algorithm).unwrap();
+    trace.blocks[0].hash.iter().flat_map(|&word| match trace.word_bits() {
+        32 => (word as u32).to_ne_bytes().to_vec(),
+        _ => word.to_ne_bytes().to_vec(),
+    }).collect()
+}
+
+#[test]
+fn buffered_input_is_wiped() {
+    let mut sha1 = Sha1Hasher::new();
+    sha1.update(SECRET).unwrap();
+    assert_wiped(sha1, SECRET);
+
+    for algorithm in [ShaAlgorithm::SHA256, ShaAlgorithm::SHA512T(256)] {
+        let mut sha2 = Sha2Hasher::new(&algorithm).unwrap();
+        sha2.update(SECRET).unwrap();
+        assert_wiped(sha2, SECRET);
+    }
+
+    let mut engine = Sha2Engine::<Sha512>::new();
+    engine.update(SECRET).unwrap();
+    assert_wiped(engine, SECRET);
+}
+
+#[test]
+fn chaining_values_are_wiped() {
+    let msg: Vec<u8> = SECRET.iter().cycle().take(150).copied().collect();
+
+    let mut sha1 = Sha1Hasher::new();
+    sha1.update(&msg[..64]).unwrap();
+    assert_wiped(sha1, &first_chaining_value(&msg, ShaAlgorithm::SHA1));
+
+    let mut sha256 = Sha2Hasher::new(&ShaAlgorithm::SHA256).unwrap();
+    sha256.update(&msg[..64]).unwrap();
+    assert_wiped(sha256, &first_chaining_value(&msg, ShaAlgorithm::SHA256));
+
+    let mut sha384 = Sha2Hasher::new(&ShaAlgorithm::SHA384).unwrap();
+    sha384.update(&msg[..128]).unwrap();
+    assert_wiped(sha384, &first_chaining_value(&msg, ShaAlgorithm::SHA384));
+}
+
+#[test]
+fn sponge_state_is_wiped() {
+    // Before the first permutation the state is the input itself
+    let mut sha3 = Sha3Hasher::new(&ShaAlgorithm::SHA3_256).unwrap();
+    sha3.update(SECRET).unwrap();
+    assert_wiped(sha3, SECRET);
+
+    let mut sponge = Sponge::new(136).unwrap();
+    sponge.absorb(SECRET).unwrap();
+    assert_wiped(sponge, SECRET);
+}
+
+#[test]
+fn mac_state_is_wiped() {
+    // The keyed inner and outer states both live in the Hmac
+    let key = b"hmac key";
+    let mut mac = Hmac::new(&ShaAlgorithm::SHA256, key).unwrap();
+    mac.update(SECRET).unwrap();
+    assert_wiped(mac, SECRET);
+
+    let mut padded_key = [0x36u8; 64];
+    for (byte, key_byte) in padded_key.iter_mut().zip(key) {
+        *byte ^= key_byte;
+    }
+    let mut inner = padded_key.to_vec();
+    inner.extend_from_slice(&[0; 64]);
+    let mac = Hmac::new(&ShaAlgorithm::SHA256, key).unwrap();
+    assert_wiped(mac, &first_chaining_value(&inner, ShaAlgorithm::SHA256));
+
+    // KMAC absorbs the key into its 200-byte sponge state, which is permuted and
+    // so nearly all nonzero; only the rate and domain suffix remain after the drop
+    let kmac = Kmac::kmac128(SECRET, b"").unwrap();
+    let (before, after) = storage_around_drop(kmac);
+    let nonzero = |bytes: &[u8]| bytes.iter().filter(|&&byte| byte != 0).count();
+    assert!(nonzero(&before) > 150);
+    assert!(nonzero(&after) < 16, "the KMAC state survived the drop");
+}